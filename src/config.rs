@@ -1,8 +1,16 @@
+use std::collections::HashMap;
 use std::fmt::Display;
 
 use serde_derive::Deserialize;
 
-use crate::{utils::file::try_read_files_from_dir_to_bytes, debug, engine::{tokenizer::TokenSet, reusability::ReusableDeclarations, generator::{TSGen, GoGen, RustGen}}};
+use crate::{utils::file::try_read_files_from_dir_to_bytes, debug};
+use crate::engine::{ast::Annotation, tokenizer::TokenSet, lexer::tokenize, 
+    parser::Parser};
+use crate::engine::reusability::{ReusableDeclarations, resolve_deprecations};
+use crate::engine::generator::{
+    TSGen, GoGen, RustGen, GeneratorOptions, TypeMapping, WideIntegers};
+use crate::engine::diagnostics::{
+    Diagnostic, DiagnosticKind, Diagnostics, SourceMap};
 
 /// Configuration extracted from command-line arguments or made up from data 
 /// contained insude `TranspilerExternalConfig`.
@@ -10,7 +18,10 @@ pub struct CommandLineInstructions {
     pub transpile_to_lang: String,
     pub transpile_dir_path: String,
     pub transpile_to_output: String,
-    pub go_module_name: Option<String>
+    pub go_module_name: Option<String>,
+    pub generator_options: GeneratorOptions,
+    /// Prints the deprecated items of the sources before generating them.
+    pub list_deprecated: bool
 }
 
 /// Configuration of the transpiler and it's generators from a tc.conf.yaml
@@ -18,130 +29,377 @@ pub struct CommandLineInstructions {
 #[derive(Deserialize)]
 pub struct TranspilerExternalConfig {
     pub ts: Option<ClassicGeneratorConfig>,
-    pub rs: Option<ClassicGeneratorConfig>,
-    pub go: Option<GoGeneratorConfig>
+    pub rs: Option<RustGeneratorConfig>,
+    pub go: Option<GoGeneratorConfig>,
+    /// Name of the field holding tagged unions constant names, shared by
+    /// every generator.
+    pub tag_field: Option<String>,
+    /// Type of the integers TypeScript and Go numbers can't hold, as
+    /// `number`, `bigint` or `string`.
+    pub wide_integers: Option<String>
 }
 
 /// Classic configuration of a generator.
 #[derive(Deserialize, Clone)]
 pub struct ClassicGeneratorConfig {
-    pub output_file: String    
+    pub output_file: String,
+    #[serde(default)]
+    pub types: WellKnownTypesConfig
+}
+
+/// Rust custom generator configuration.
+#[derive(Deserialize, Clone)]
+pub struct RustGeneratorConfig {
+    pub output_file: String,
+    #[serde(default)]
+    pub serde: bool,
+    #[serde(default)]
+    pub btree_map: bool,
+    #[serde(default)]
+    pub types: WellKnownTypesConfig
 }
 
 /// Go custom generator configuration.
 #[derive(Deserialize, Clone)]
 pub struct GoGeneratorConfig {
     pub output_file: String,
-    pub module_name: String
+    pub module_name: String,
+    #[serde(default)]
+    pub types: WellKnownTypesConfig
+}
+
+/// Mappings of well-known types replacing the ones of a generator.
+#[derive(Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WellKnownTypesConfig {
+    pub bytes: Option<TypeMappingConfig>,
+    pub uuid: Option<TypeMappingConfig>,
+    pub datetime: Option<TypeMappingConfig>,
+    pub date: Option<TypeMappingConfig>,
+    pub duration: Option<TypeMappingConfig>,
+    pub decimal: Option<TypeMappingConfig>
+}
+
+/// Type a well-known type is mapped to, along with the imports it requires.
+#[derive(Deserialize, Clone)]
+pub struct TypeMappingConfig {
+    #[serde(rename = "type")]
+    pub target: String,
+    #[serde(default)]
+    pub imports: Vec<String>
+}
+
+impl WellKnownTypesConfig {
+    /// Mappings indexed by the keyword of their well-known type.
+    fn type_mappings(&self) -> HashMap<String, TypeMapping> {
+        [("bytes", &self.bytes), ("uuid", &self.uuid), 
+         ("datetime", &self.datetime), ("date", &self.date), 
+         ("duration", &self.duration), ("decimal", &self.decimal)]
+            .into_iter()
+            .filter_map(|(keyword, mapping)| mapping.as_ref()
+                .map(|mapping| (keyword.to_string(), TypeMapping { 
+                    target: mapping.target.clone(), 
+                    imports: mapping.imports.clone() })))
+            .collect()
+    }
 }
 
 impl CommandLineInstructions {
-    /// Transpiles content from command-line instructions.
-    pub fn transpile(&self) {
-        let files = try_read_files_from_dir_to_bytes(self.transpile_dir_path.clone())
-            .expect("Failed to read files from the provided directory.");
-
-        // Transforms the `files` variable into a set of lines to process. 
-        // Comments line are skipped here.
-        // Transforms the files lines into a set of tokens.
-        let tokenized_lines = TokenSet::apply_hierarchy_rules(files.iter()
-            .map(|f| String::from_utf8_lossy(f))
-            .collect::<String>()
-            .trim()
-            .split("\n")
-            .filter(|l| !l.starts_with(":"))
-            .map(|e| TokenSet::token_set_from_string(e.clone().to_string()))
-            .filter(|i| i.is_some())
-            .map(|f| f.unwrap())
-            .collect::<Vec<TokenSet>>());
+    /// Transpiles content from command-line instructions. Every problem found
+    /// along the way is reported, and `false` is returned if any of them is
+    /// an error.
+    pub fn transpile(&self) -> bool {
+        let mut diagnostics = Diagnostics::default();
+        let mut sources = SourceMap::default();
+
+        let files = match try_read_files_from_dir_to_bytes(
+            self.transpile_dir_path.clone()) {
+            Ok(files) => files,
+            Err(reason) => {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::SourceReadFailed(reason), None));
+                diagnostics.report(&sources);
+                return false;
+            }
+        };
+
+        debug!("File(s) content: {:?}", files);
+
+        // Every file is parsed on its own, the modules they contain are then
+        // turned into a set of tokens.
+        let mut modules = vec![];
+
+        for (path, content) in files {
+            let content = String::from_utf8_lossy(&content).to_string();
+            let lexemes = tokenize(&content, &path, &mut diagnostics);
+
+            let mut parser = Parser::new(&lexemes, &mut diagnostics);
+            modules.extend(parser.parse_file());
+            sources.add_file(path, content);
+        }
+
+        let mut tokenized_lines = TokenSet::token_sets_from_ast(&modules);
         let reusability_data = ReusableDeclarations::from_token_sets_vec(
             tokenized_lines.clone());
 
+        // Deprecated items are listed before field groups are expanded into
+        // structs, and even when the sources have errors.
+        resolve_deprecations(&mut tokenized_lines, &mut diagnostics);
+        if self.list_deprecated {
+            Self::list_deprecated_items(&tokenized_lines);
+        }
+
+        reusability_data.resolve_local_types(
+            &mut tokenized_lines, &mut diagnostics);
 
-        debug!("File(s) content: {:?}", files);
+        if !diagnostics.has_errors() {
+            self.check_annotations(&tokenized_lines, &mut diagnostics);
 
-        let result = match self.transpile_to_lang.as_str() {
+            if let Err(diagnostic) = self.generate(
+                tokenized_lines, reusability_data) {
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        diagnostics.report(&sources);
+
+        if diagnostics.has_errors() {
+            println!("Producing an output failed with {} error(s).",
+                     diagnostics.error_count());
+            return false;
+        } 
+
+        println!("Successfully produced an output at {} in {}",
+                 self.transpile_to_output,
+                 self.transpile_to_lang);
+        return true;
+    }
+
+    // Reports the annotations the generator matching `transpile_to_lang`
+    // doesn't act on, once each as fields of field groups are copied.
+    fn check_annotations(
+        &self, 
+        tokenized_lines: &[TokenSet], 
+        diagnostics: &mut Diagnostics) {
+        let known_annotations = match self.transpile_to_lang.as_str() {
+            #[cfg(feature = "ts-gen")]
+            "ts" => <TokenSet as TSGen>::known_annotations(),
+            #[cfg(feature = "go-gen")]
+            "go" => <TokenSet as GoGen>::known_annotations(),
+            #[cfg(feature = "rust-gen")]
+            "rs" => <TokenSet as RustGen>::known_annotations(),
+            _ => return
+        };
+        let mut reported: Vec<&Annotation> = vec![];
+
+        for annotation in tokenized_lines.iter()
+            .flat_map(|token| token.annotations_in_tree()) {
+            if known_annotations.contains(&annotation.name.name.as_str()) ||
+                reported.contains(&annotation) { continue; }
+            reported.push(annotation);
+
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::UnknownAnnotation { 
+                    name: annotation.name.name.clone(), 
+                    language: self.transpile_to_lang.clone() },
+                Some(annotation.span.clone())));
+        }
+    }
+
+    // Prints the deprecated declarations, fields and enum constants, along
+    // with where they are declared and their deprecation message.
+    fn list_deprecated_items(tokenized_lines: &[TokenSet]) {
+        let items = tokenized_lines.iter()
+            .flat_map(|module| module.deprecated_in_tree(""))
+            .collect::<Vec<(String, &TokenSet)>>();
+
+        println!("Found {} deprecated item(s).", items.len());
+        for (path, token) in items {
+            println!("    {} at {}: {}", 
+                     path, token.span, token.deprecation.as_ref().unwrap());
+        }
+    }
+
+    // Runs the generator matching `transpile_to_lang` over the tokens.
+    fn generate(
+        &self, 
+        tokenized_lines: Vec<TokenSet>, 
+        reusability_data: ReusableDeclarations) -> Result<(), Diagnostic> {
+        match self.transpile_to_lang.as_str() {
             #[cfg(feature = "ts-gen")]
             "ts" => {
                 <TokenSet as TSGen>::produce_ts_build_in_single_file(
-                    tokenized_lines.clone(), 
+                    tokenized_lines, 
                     reusability_data, 
-                    self.transpile_to_output.clone())
+                    self.transpile_to_output.clone(),
+                    &self.generator_options)
             },
             #[cfg(feature = "go-gen")]
             "go" => {
+                let go_module_name = self.go_module_name.clone()
+                    .ok_or_else(|| Diagnostic::error(
+                        DiagnosticKind::MissingGeneratorOption(
+                            "go_module_name".into()), 
+                        None))?;
+
                 <TokenSet as GoGen>::produce_go_build_in_single_file(
-                    tokenized_lines.clone(), 
+                    tokenized_lines, 
                     reusability_data, 
                     self.transpile_to_output.clone(), 
-                    self.go_module_name.clone().unwrap())
+                    go_module_name,
+                    &self.generator_options)
             },
             #[cfg(feature = "rust-gen")]
             "rs" => {
                 <TokenSet as RustGen>::produce_rs_build_in_single_file(
-                    tokenized_lines.clone(), 
+                    tokenized_lines, 
                     reusability_data, 
-                    self.transpile_to_output.clone())
+                    self.transpile_to_output.clone(),
+                    &self.generator_options)
             },
-            _ => { panic!("Unknown generator: {}.", self.transpile_to_lang) }
-        };
-
-        if result.is_err() {
-            println!("Producing an output failed: {}", result.err().unwrap());
-        } else {
-            println!("Successfully produced an output at {} in {}",
-                     self.transpile_to_output,
-                     self.transpile_to_lang);
+            _ => Err(Diagnostic::error(
+                DiagnosticKind::UnknownGenerator(
+                    self.transpile_to_lang.clone()), 
+                None))
         }
     }
 }
 
 impl TranspilerExternalConfig {
+    /// Instructions to transpile `dir` to `for_lang`, if the configuration
+    /// has a section for it. Unknown languages and invalid values are
+    /// reported.
     pub fn make_command_line_instruction<T: Display>(
-        &self, for_lang: T, dir: T) -> Option<CommandLineInstructions> {
+        &self, for_lang: T, dir: T) 
+        -> Result<Option<CommandLineInstructions>, Diagnostic> {
+        let base_generator_options = self.base_generator_options()?;
+
         match for_lang.to_string().as_str() {
             "rs" => { 
-                if self.rs.is_none() { return None; }
-                let transpile_to_output = format!("{}/{}", 
-                        dir.to_string(),
-                        self.rs.clone().unwrap().output_file);
+                let rs = match self.rs.clone() {
+                    Some(rs) => rs,
+                    None => return Ok(None)
+                };
+                let transpile_to_output = format!("{}/{}", dir, rs.output_file);
 
- 
-                Some(CommandLineInstructions {
+                Ok(Some(CommandLineInstructions {
                     transpile_to_lang: "rs".into(),
                     transpile_to_output,
                     transpile_dir_path: dir.to_string(),
-                    go_module_name: None
-                })
+                    go_module_name: None,
+                    list_deprecated: false,
+                    generator_options: GeneratorOptions {
+                        serde: rs.serde,
+                        btree_map: rs.btree_map,
+                        type_mappings: rs.types.type_mappings(),
+                        ..base_generator_options
+                    }
+                }))
             },
             "ts" => {
-                if self.ts.is_none() { return None; }
-                let transpile_to_output = format!("{}/{}", 
-                        dir.to_string(),
-                        self.ts.clone().unwrap().output_file);
+                let ts = match self.ts.clone() {
+                    Some(ts) => ts,
+                    None => return Ok(None)
+                };
+                let transpile_to_output = format!("{}/{}", dir, ts.output_file);
 
-                Some(CommandLineInstructions {
+                Ok(Some(CommandLineInstructions {
                     transpile_to_lang: "ts".into(),
                     transpile_to_output,
                     transpile_dir_path: dir.to_string(),
-                    go_module_name: None
-                })
+                    go_module_name: None,
+                    list_deprecated: false,
+                    generator_options: GeneratorOptions {
+                        type_mappings: ts.types.type_mappings(),
+                        ..base_generator_options
+                    }
+                }))
             },
             "go" => {
-                if self.ts.is_none() { return None; }
-                let transpile_to_output = format!("{}/{}", 
-                        dir.to_string(),
-                        self.go.clone().unwrap().output_file);
+                let go = match self.go.clone() {
+                    Some(go) => go,
+                    None => return Ok(None)
+                };
+                let transpile_to_output = format!("{}/{}", dir, go.output_file);
 
-                Some(CommandLineInstructions {
+                Ok(Some(CommandLineInstructions {
                     transpile_to_lang: "go".into(),
                     transpile_to_output,
                     transpile_dir_path: dir.to_string(),
-                    go_module_name: Some(self.go.clone().unwrap().module_name)
-                })
+                    go_module_name: Some(go.module_name),
+                    list_deprecated: false,
+                    generator_options: GeneratorOptions {
+                        type_mappings: go.types.type_mappings(),
+                        ..base_generator_options
+                    }
+                }))
             },
-            _ => { panic!("Unknown generator in config: {}",
-                            for_lang.to_string()); }
+            _ => Err(Diagnostic::error(
+                DiagnosticKind::UnknownGenerator(for_lang.to_string()), None))
+        }
+    }
+
+    /// Generator options shared by every language.
+    fn base_generator_options(&self) -> Result<GeneratorOptions, Diagnostic> {
+        let default = GeneratorOptions::default();
+        let wide_integers = match &self.wide_integers {
+            Some(keyword) => WideIntegers::from_keyword(keyword)
+                .ok_or_else(|| Diagnostic::error(
+                    DiagnosticKind::InvalidConfigValue { 
+                        key: "wide_integers".into(), value: keyword.clone() },
+                    None)
+                    .with_note("expected `number`, `bigint` or `string`"))?,
+            None => default.wide_integers
+        };
+
+        Ok(GeneratorOptions {
+            tag_field: self.tag_field.clone().unwrap_or(default.tag_field),
+            wide_integers,
+            ..default
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(wide_integers: Option<&str>) -> TranspilerExternalConfig {
+        TranspilerExternalConfig {
+            ts: Some(ClassicGeneratorConfig { 
+                output_file: "types.ts".into(), types: Default::default() }),
+            rs: None,
+            go: None,
+            tag_field: None,
+            wide_integers: wide_integers.map(String::from)
         }
     }
+
+    #[test]
+    fn instructions_of_configured_languages() {
+        let config = config(Some("bigint"));
+        let instruction = config.make_command_line_instruction("ts", "src")
+            .unwrap().unwrap();
+
+        assert_eq!(instruction.transpile_to_output, "src/types.ts");
+        assert_eq!(instruction.generator_options.wide_integers, 
+                   WideIntegers::BigInt);
+        assert!(config.make_command_line_instruction("rs", "src")
+            .unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_values_are_reported() {
+        let diagnostic = config(Some("float")).make_command_line_instruction(
+            "ts", "src").err().unwrap();
+
+        assert_eq!(diagnostic.kind, DiagnosticKind::InvalidConfigValue { 
+            key: "wide_integers".into(), value: "float".into() });
+
+        let diagnostic = config(None).make_command_line_instruction(
+            "py", "src").err().unwrap();
+
+        assert_eq!(diagnostic.kind, 
+                   DiagnosticKind::UnknownGenerator("py".into()));
+    }
 }
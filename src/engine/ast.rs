@@ -0,0 +1,331 @@
+use std::fmt::{self, Display};
+
+use super::diagnostics::SourceSpan;
+use super::tokenizer::TokenParameter;
+
+// Abstract syntax tree of a TypeCode file, as built by the parser. Nodes only
+// hold what has been written, types are resolved when `TokenSet`s are derived
+// from it.
+
+// A name along with its location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ident {
+    pub name: String,
+    pub span: SourceSpan
+}
+
+// A module, written as `module [name]`. Modules nested in other modules are
+// written with their full path: `module [name].[name]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Module {
+    pub path: Vec<Ident>,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub items: Vec<Item>,
+    pub span: SourceSpan
+}
+
+// Declarations that can be placed inside of a module.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Struct(Struct),
+    Enum(Enum),
+    Alias(Alias),
+    NewType(Alias),
+    Const(Const),
+    Extern(Extern),
+    FieldGroup(FieldGroup)
+}
+
+// A struct, generic over its type parameters when it has some and written as
+// `struct [name] extends [name], [name]` when it extends other structs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Struct {
+    pub name: Ident,
+    pub type_parameters: Vec<Ident>,
+    // Structs whose fields are added to the ones of the struct.
+    pub bases: Vec<Ident>,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub members: Vec<Member>,
+    pub span: SourceSpan
+}
+
+// A group of fields declared once to be included in structs, written as
+// `fields [name]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldGroup {
+    pub name: Ident,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub members: Vec<Member>,
+    pub span: SourceSpan
+}
+
+// A line of a struct or of a field group: a field, or `include [name]` to
+// pull the fields of a field group.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Member {
+    Field(Box<Field>),
+    Include(Ident)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+    pub name: Ident,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub variants: Vec<Variant>,
+    pub span: SourceSpan
+}
+
+// An alias or a newtype, written as `[alias | newtype] [name] = [type]
+// [parameters]`. Aliases are other names for their type, while newtypes are
+// distinct types wrapping it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alias {
+    pub name: Ident,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub target: TypeRef,
+    pub span: SourceSpan
+}
+
+// A constant of a primitive type, written as `const [type] [name] = [value]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Const {
+    pub name: Ident,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub const_type: Ident,
+    pub value: (Literal, SourceSpan),
+    pub span: SourceSpan
+}
+
+// A type declared outside of TypeCode, written as `extern [name]` followed by
+// a line per language it's mapped to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extern {
+    pub name: Ident,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub mappings: Vec<ExternMapping>,
+    pub span: SourceSpan
+}
+
+// The type an extern type stands for in a language, written as `[language]
+// [text]`, optionally followed by `import [text], [text]` to list what the
+// type requires.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternMapping {
+    pub language: Ident,
+    pub target: String,
+    pub imports: Vec<String>,
+    pub span: SourceSpan
+}
+
+// A struct's field, written as `[type] [parameters] [name]`, optionally
+// followed by `= [value]` to give it a default value and by `[[constraint],
+// [constraint]]` to validate it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub field_type: TypeRef,
+    pub name: Ident,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub default: Option<(Literal, SourceSpan)>,
+    pub constraints: Vec<(Constraint, SourceSpan)>,
+    pub span: SourceSpan
+}
+
+// Metadata attached to the node below it, written as `@[name]` or 
+// `@[name]([argument], [argument])`. Generators act on the annotations they
+// know, the other ones being reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub name: Ident,
+    pub arguments: Vec<AnnotationArgument>,
+    pub span: SourceSpan
+}
+
+// A value given to an annotation, written as `[literal]` or, when named, as
+// `[name]: [literal]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationArgument {
+    pub name: Option<Ident>,
+    pub value: (Literal, SourceSpan)
+}
+
+// A rule the value of a field must follow, checked by the generated
+// validation code.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Constraint {
+    // `min([number])`, the lowest value of a number.
+    Min(Literal),
+    // `max([number])`, the highest value of a number.
+    Max(Literal),
+    // `length([min], [max])`, the number of characters of a string or of
+    // items of a collection.
+    Length(u64, u64),
+    // `pattern([text])`, a regular expression a string must match.
+    Pattern(String),
+    // `non_empty`, a string or a collection must have content.
+    NonEmpty
+}
+
+// A type along with its parameters, written as `[name] [parameters]`. Types
+// such as `map` are given type arguments: `[name]<[type], [type]>`, arrays
+// being given their length as well: `array<[type], [length]>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeRef {
+    pub name: Ident,
+    pub arguments: Vec<TypeRef>,
+    pub array_length: Option<usize>,
+    pub parameters: Vec<(TokenParameter, SourceSpan)>,
+    pub span: SourceSpan
+}
+
+// An enum's constant, written as `[name]` or `[name] = [value]`. Constants of
+// tagged unions can also carry a payload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub name: Ident,
+    pub value: Option<(Literal, SourceSpan)>,
+    pub payload: Option<VariantPayload>,
+    pub docs: Vec<String>,
+    pub annotations: Vec<Annotation>,
+    pub span: SourceSpan
+}
+
+// Data carried by a tagged union constant.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantPayload {
+    // `[name]([struct])`, the constant wraps a struct declared in TypeCode.
+    Struct(Ident),
+    // `[name] { [field], [field] }`, the constant holds its own fields.
+    Fields(Vec<Field>)
+}
+
+// A value written in a TypeCode file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Integer(i128),
+    Float(f64),
+    Text(String),
+    Bool(bool)
+}
+
+impl Literal {
+    // Writes the value as a literal of `language`, among `rs`, `go` and `ts`.
+    // Only strings are written differently by each language.
+    pub fn to_code(&self, language: &str) -> String {
+        match self {
+            Literal::Text(value) => quote(value, language),
+            value => value.to_string()
+        }
+    }
+}
+
+// Describes the value the way it is written, to be used in diagnostics.
+impl Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Integer(value) => write!(f, "{}", value),
+            Literal::Float(value) => write!(f, "{:?}", value),
+            Literal::Text(value) => write!(f, "{:?}", value),
+            Literal::Bool(value) => write!(f, "{}", value)
+        }
+    }
+}
+
+// Writes `text` as a string literal of `language`. Go and TypeScript don't
+// read the `\u{...}` escapes of Rust, their control characters are written
+// as `\x` escapes instead.
+pub fn quote(text: &str, language: &str) -> String {
+    if language == "rs" { return format!("{:?}", text); }
+
+    return format!("\"{}\"", escape(text, '"'));
+}
+
+// Writes `character` as a character literal of `language`, TypeScript
+// having strings only.
+pub fn quote_char(character: char, language: &str) -> String {
+    match language {
+        "rs" => format!("{:?}", character),
+        "go" => format!("'{}'", escape(&character.to_string(), '\'')),
+        _ => quote(&character.to_string(), language)
+    }
+}
+
+// Escapes `text` for Go and TypeScript literals delimited by `delimiter`.
+fn escape(text: &str, delimiter: char) -> String {
+    let mut output = String::new();
+
+    for character in text.chars() {
+        match character {
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            character if character == delimiter => {
+                output.push('\\');
+                output.push(character);
+            }
+            character if character.is_ascii_control() => 
+                output.push_str(&format!("\\x{:02x}", character as u32)),
+            character => output.push(character)
+        }
+    }
+    return output;
+}
+
+impl Constraint {
+    // Describes the constraint the way it is written, to be used in
+    // validation errors.
+    pub fn describe(&self) -> String {
+        match self {
+            Constraint::Min(value) => format!("min({})", value),
+            Constraint::Max(value) => format!("max({})", value),
+            Constraint::Length(min, max) => format!("length({}, {})", min, max),
+            Constraint::Pattern(pattern) => format!("pattern({:?})", pattern),
+            Constraint::NonEmpty => "non_empty".into()
+        }
+    }
+}
+
+impl Item {
+    pub fn span(&self) -> &SourceSpan {
+        match self {
+            Item::Struct(item) => &item.span,
+            Item::Enum(item) => &item.span,
+            Item::Alias(item) | Item::NewType(item) => &item.span,
+            Item::Const(item) => &item.span,
+            Item::Extern(item) => &item.span,
+            Item::FieldGroup(item) => &item.span
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_escaped_per_language() {
+        let text = Literal::Text("a\\b\"c\td\u{1}e\u{301}".into());
+
+        assert_eq!(text.to_code("rs"), r#""a\\b\"c\td\u{1}e\u{301}""#);
+        assert_eq!(text.to_code("go"), "\"a\\\\b\\\"c\\td\\x01e\u{301}\"");
+        assert_eq!(text.to_code("ts"), "\"a\\\\b\\\"c\\td\\x01e\u{301}\"");
+        assert_eq!(Literal::Float(1e5).to_code("go"), "100000.0");
+    }
+
+    #[test]
+    fn characters_are_escaped_per_language() {
+        assert_eq!(quote_char('\'', "rs"), r"'\''");
+        assert_eq!(quote_char('\'', "go"), r"'\''");
+        assert_eq!(quote_char('"', "go"), "'\"'");
+        assert_eq!(quote_char('\'', "ts"), "\"'\"");
+        assert_eq!(quote_char('\u{7f}', "go"), r"'\x7f'");
+    }
+}
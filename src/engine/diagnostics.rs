@@ -0,0 +1,458 @@
+use std::fmt::{self, Display};
+
+use super::tokenizer::TokenType;
+
+// How bad a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning
+}
+
+// Location of a piece of TypeCode source. Lines and columns start at 1, the
+// length is counted in characters.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SourceSpan {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub length: usize
+}
+
+// Every problem the transpiler knows how to report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticKind {
+    // Lexer errors.
+    UnexpectedCharacter(char),
+    UnterminatedString,
+    // Parser errors.
+    UnexpectedToken { expected: String, found: String },
+    MissingDeclarationName(TokenType),
+    MissingFieldName,
+    UnknownParameter(String),
+    InvalidLiteral(String),
+    TypeArgumentCount { type_name: String, expected: usize, found: usize },
+    FloatedOnNonInteger(String),
+    InvalidArrayLength(String),
+    // Enum values errors.
+    InvalidEnumValue,
+    MixedEnumValues,
+    MissingEnumValue,
+    DuplicateEnumValue(String),
+    EnumValueOutOfRange(String),
+    ValuedTaggedUnion,
+    // Constants and default values errors.
+    MismatchedValue { value: String, type_name: String },
+    NonPrimitiveConst(String),
+    UnsupportedDefault(String),
+    UnknownExternLanguage(String),
+    // Constraints errors.
+    UnknownConstraint(String),
+    InapplicableConstraint { constraint: String, type_name: String },
+    InvalidConstraintArguments { constraint: String, expected: String },
+    InvalidPattern(String),
+    // Annotations errors.
+    InvalidAnnotationArguments { annotation: String, expected: String },
+    // Hierarchy errors.
+    DeclarationOutsideModule(TokenType),
+    FieldOutsideDeclaration,
+    InvalidEnumVariant,
+    // Resolution errors.
+    UnresolvedLocalType(String),
+    AmbiguousLocalType { name: String, candidates: Vec<String> },
+    LocalOnBuiltinType(TokenType),
+    PayloadNotStruct(String),
+    InvalidMapKey(String),
+    InvalidSetItem(String),
+    RecursiveAlias(String),
+    BaseNotStruct(String),
+    GenericBase(String),
+    RecursiveBase(String),
+    DuplicateField { name: String, origin: String },
+    NotFieldGroup(String),
+    FieldGroupAsType(String),
+    RecursiveFieldGroup(String),
+    DuplicateSerializedName { name: String, field: String },
+    // Generation errors.
+    UnknownGenerator(String),
+    MissingGeneratorOption(String),
+    SourceReadFailed(String),
+    OutputWriteFailed(String),
+    MissingExternMapping { name: String, language: String },
+    // Configuration errors.
+    InvalidConfigValue { key: String, value: String },
+    // Deprecation warnings.
+    DeprecatedFloated,
+    // Generation warnings.
+    UnknownAnnotation { name: String, language: String }
+}
+
+// A problem found while processing TypeCode files, attached to the place of
+// the source it originates from when there is one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    pub span: Option<SourceSpan>,
+    pub notes: Vec<String>
+}
+
+// Collects diagnostics across every processing step of a transpilation.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    pub entries: Vec<Diagnostic>
+}
+
+// Content of the files being processed, used to render code snippets.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    pub files: Vec<(String, String)>
+}
+
+impl DiagnosticKind {
+    // Stable code of the diagnostic, printed next to its severity.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DiagnosticKind::UnexpectedCharacter(_) => "E0001",
+            DiagnosticKind::UnterminatedString => "E0002",
+            DiagnosticKind::UnexpectedToken { .. } => "E0003",
+            DiagnosticKind::MissingDeclarationName(_) => "E0004",
+            DiagnosticKind::MissingFieldName => "E0005",
+            DiagnosticKind::UnknownParameter(_) => "E0006",
+            DiagnosticKind::InvalidLiteral(_) => "E0007",
+            DiagnosticKind::TypeArgumentCount { .. } => "E0008",
+            DiagnosticKind::FloatedOnNonInteger(_) => "E0009",
+            DiagnosticKind::InvalidArrayLength(_) => "E0010",
+            DiagnosticKind::InvalidEnumValue => "E0011",
+            DiagnosticKind::MixedEnumValues => "E0012",
+            DiagnosticKind::MissingEnumValue => "E0013",
+            DiagnosticKind::DuplicateEnumValue(_) => "E0014",
+            DiagnosticKind::EnumValueOutOfRange(_) => "E0015",
+            DiagnosticKind::ValuedTaggedUnion => "E0016",
+            DiagnosticKind::MismatchedValue { .. } => "E0017",
+            DiagnosticKind::NonPrimitiveConst(_) => "E0018",
+            DiagnosticKind::UnsupportedDefault(_) => "E0019",
+            DiagnosticKind::UnknownExternLanguage(_) => "E0020",
+            DiagnosticKind::UnknownConstraint(_) => "E0021",
+            DiagnosticKind::InapplicableConstraint { .. } => "E0022",
+            DiagnosticKind::InvalidConstraintArguments { .. } => "E0023",
+            DiagnosticKind::InvalidPattern(_) => "E0024",
+            DiagnosticKind::InvalidAnnotationArguments { .. } => "E0025",
+            DiagnosticKind::DeclarationOutsideModule(_) => "E0101",
+            DiagnosticKind::FieldOutsideDeclaration => "E0102",
+            DiagnosticKind::InvalidEnumVariant => "E0103",
+            DiagnosticKind::UnresolvedLocalType(_) => "E0201",
+            DiagnosticKind::LocalOnBuiltinType(_) => "E0202",
+            DiagnosticKind::PayloadNotStruct(_) => "E0203",
+            DiagnosticKind::InvalidMapKey(_) => "E0204",
+            DiagnosticKind::RecursiveAlias(_) => "E0205",
+            DiagnosticKind::AmbiguousLocalType { .. } => "E0206",
+            DiagnosticKind::InvalidSetItem(_) => "E0207",
+            DiagnosticKind::BaseNotStruct(_) => "E0208",
+            DiagnosticKind::GenericBase(_) => "E0209",
+            DiagnosticKind::RecursiveBase(_) => "E0210",
+            DiagnosticKind::DuplicateField { .. } => "E0211",
+            DiagnosticKind::NotFieldGroup(_) => "E0212",
+            DiagnosticKind::FieldGroupAsType(_) => "E0213",
+            DiagnosticKind::RecursiveFieldGroup(_) => "E0214",
+            DiagnosticKind::DuplicateSerializedName { .. } => "E0215",
+            DiagnosticKind::UnknownGenerator(_) => "E0301",
+            DiagnosticKind::MissingGeneratorOption(_) => "E0302",
+            DiagnosticKind::SourceReadFailed(_) => "E0303",
+            DiagnosticKind::OutputWriteFailed(_) => "E0304",
+            DiagnosticKind::MissingExternMapping { .. } => "E0305",
+            DiagnosticKind::InvalidConfigValue { .. } => "E0401",
+            DiagnosticKind::DeprecatedFloated => "W0001",
+            DiagnosticKind::UnknownAnnotation { .. } => "W0002"
+        }
+    }
+}
+
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticKind::UnexpectedCharacter(character) =>
+                write!(f, "unexpected character `{}`", character),
+            DiagnosticKind::UnterminatedString =>
+                write!(f, "unterminated string"),
+            DiagnosticKind::UnexpectedToken { expected, found } =>
+                write!(f, "expected {}, found {}", expected, found),
+            DiagnosticKind::MissingFieldName =>
+                write!(f, "missing field name after its type"),
+            DiagnosticKind::InvalidLiteral(literal) =>
+                write!(f, "invalid value `{}`", literal),
+            DiagnosticKind::TypeArgumentCount { type_name, expected, found } =>
+                write!(f, "`{}` takes {} type argument(s) but {} were given",
+                       type_name, expected, found),
+            DiagnosticKind::FloatedOnNonInteger(type_name) =>
+                write!(f, "`floated` cannot be applied to `{}`, it only works \
+                           with integers", type_name),
+            DiagnosticKind::InvalidEnumValue =>
+                write!(f, "enum values must be integers or strings"),
+            DiagnosticKind::MixedEnumValues =>
+                write!(f, "enum values must all be integers or all be strings"),
+            DiagnosticKind::MissingEnumValue =>
+                write!(f, "missing value for a constant of a string enum"),
+            DiagnosticKind::DuplicateEnumValue(name) =>
+                write!(f, "the value of `{}` is already used by another \
+                           constant", name),
+            DiagnosticKind::EnumValueOutOfRange(value) =>
+                write!(f, "enum value `{}` doesn't fit in a 64-bit signed \
+                           integer", value),
+            DiagnosticKind::ValuedTaggedUnion => write!(f,
+                "constants of an enum carrying data can't have values"),
+            DiagnosticKind::MismatchedValue { value, type_name } =>
+                write!(f, "`{}` is not a valid `{}` value", value, type_name),
+            DiagnosticKind::NonPrimitiveConst(type_name) =>
+                write!(f, "constants can't be of type `{}`, only strings, \
+                           characters, integers, floats and booleans", 
+                       type_name),
+            DiagnosticKind::UnsupportedDefault(type_name) =>
+                write!(f, "fields of type `{}` can't have a default value, \
+                           only strings, characters, integers, floats and \
+                           booleans can", type_name),
+            DiagnosticKind::InvalidArrayLength(length) =>
+                write!(f, "`{}` is not a valid array length", length),
+            DiagnosticKind::UnknownExternLanguage(language) =>
+                write!(f, "unknown language `{}`", language),
+            DiagnosticKind::UnknownConstraint(constraint) =>
+                write!(f, "unknown constraint `{}`", constraint),
+            DiagnosticKind::InapplicableConstraint { constraint, type_name } =>
+                write!(f, "`{}` can't be applied to fields of type `{}`",
+                       constraint, type_name),
+            DiagnosticKind::InvalidConstraintArguments { 
+                constraint, expected } =>
+                write!(f, "`{}` takes {}", constraint, expected),
+            DiagnosticKind::InvalidPattern(reason) =>
+                write!(f, "invalid pattern: {}", reason),
+            DiagnosticKind::InvalidAnnotationArguments {
+                annotation, expected } =>
+                write!(f, "`@{}` takes {}", annotation, expected),
+            DiagnosticKind::MissingDeclarationName(token_type) =>
+                write!(f, "missing name after `{}` declaration",
+                       token_type.keyword().unwrap_or_default()),
+            DiagnosticKind::UnknownParameter(parameter) =>
+                write!(f, "unknown type parameter `{}`", parameter),
+            DiagnosticKind::DeclarationOutsideModule(token_type) =>
+                write!(f, "`{}` declared outside of a module",
+                       token_type.keyword().unwrap_or_default()),
+            DiagnosticKind::FieldOutsideDeclaration =>
+                write!(f, "field declared outside of a struct or an enum"),
+            DiagnosticKind::InvalidEnumVariant =>
+                write!(f, "enum constants must be a single name"),
+            DiagnosticKind::UnresolvedLocalType(name) =>
+                write!(f, "cannot find local type `{}`", name),
+            DiagnosticKind::AmbiguousLocalType { name, .. } =>
+                write!(f, "local type `{}` is declared in several modules", 
+                       name),
+            DiagnosticKind::LocalOnBuiltinType(token_type) =>
+                write!(f, "`local` cannot be applied to built-in type `{}`",
+                       token_type.keyword().unwrap_or_default()),
+            DiagnosticKind::PayloadNotStruct(name) =>
+                write!(f, "`{}` is not a struct declared in TypeCode", name),
+            DiagnosticKind::InvalidMapKey(name) =>
+                write!(f, "`{}` can't be used as a map key", name),
+            DiagnosticKind::InvalidSetItem(name) =>
+                write!(f, "`{}` can't be used as a set item", name),
+            DiagnosticKind::RecursiveAlias(name) =>
+                write!(f, "alias `{}` stands for itself", name),
+            DiagnosticKind::BaseNotStruct(name) =>
+                write!(f, "`{}` is not a struct declared in TypeCode, it \
+                           can't be extended", name),
+            DiagnosticKind::GenericBase(name) =>
+                write!(f, "generic struct `{}` can't be extended", name),
+            DiagnosticKind::RecursiveBase(name) =>
+                write!(f, "struct `{}` extends itself", name),
+            DiagnosticKind::DuplicateField { name, origin } =>
+                write!(f, "field `{}` is already declared by `{}`", 
+                       name, origin),
+            DiagnosticKind::NotFieldGroup(name) =>
+                write!(f, "`{}` is not a field group declared in TypeCode", 
+                       name),
+            DiagnosticKind::FieldGroupAsType(name) =>
+                write!(f, "field group `{}` can't be used as a type", name),
+            DiagnosticKind::RecursiveFieldGroup(name) =>
+                write!(f, "field group `{}` includes itself", name),
+            DiagnosticKind::DuplicateSerializedName { name, field } =>
+                write!(f, "serialized name `{}` is already used by field `{}`",
+                       name, field),
+            DiagnosticKind::UnknownGenerator(lang) =>
+                write!(f, "unknown generator `{}`", lang),
+            DiagnosticKind::MissingGeneratorOption(option) =>
+                write!(f, "missing generator option `{}`", option),
+            DiagnosticKind::SourceReadFailed(reason) =>
+                write!(f, "failed to read source files: {}", reason),
+            DiagnosticKind::OutputWriteFailed(reason) =>
+                write!(f, "failed to write the output file: {}", reason),
+            DiagnosticKind::MissingExternMapping { name, language } =>
+                write!(f, "extern type `{}` has no `{}` mapping", 
+                       name, language),
+            DiagnosticKind::InvalidConfigValue { key, value } =>
+                write!(f, "invalid value `{}` for `{}` in configuration", 
+                       value, key),
+            DiagnosticKind::DeprecatedFloated =>
+                write!(f, "the `floated` parameter is deprecated"),
+            DiagnosticKind::UnknownAnnotation { name, language } =>
+                write!(f, "annotation `@{}` is unknown to the `{}` generator \
+                           and is ignored", name, language)
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning")
+        }
+    }
+}
+
+impl Diagnostic {
+    pub fn error(kind: DiagnosticKind, span: Option<SourceSpan>) -> Self {
+        Self { severity: Severity::Error, kind, span, notes: vec![] }
+    }
+
+    pub fn warning(kind: DiagnosticKind, span: Option<SourceSpan>) -> Self {
+        Self { severity: Severity::Warning, kind, span, notes: vec![] }
+    }
+
+    // Adds a note printed below the snippet.
+    pub fn with_note<T: Display>(mut self, note: T) -> Self {
+        self.notes.push(note.to_string());
+        self
+    }
+
+    // Renders the diagnostic the way rustc does, such as:
+    //
+    // error[E0102]: field declared outside of a struct or an enum
+    //  --> types/email.tc:2:5
+    //   |
+    // 2 |     string Email
+    //   |     ^^^^^^^^^^^^
+    pub fn render(&self, sources: &SourceMap) -> String {
+        let mut output = vec![
+            format!("{}[{}]: {}", self.severity, self.kind.code(), self.kind)];
+
+        let gutter = self.span.as_ref()
+            .map(|span| span.line.to_string().len())
+            .unwrap_or(0);
+        let padding = " ".repeat(gutter);
+
+        if let Some(span) = &self.span {
+            output.push(format!("{}--> {}", padding, span));
+
+            if let Some(line) = sources.line(&span.file, span.line) {
+                // Tabs before the span are kept, for the carets to line up
+                // with it however wide tabs are displayed.
+                let indent = line.chars()
+                    .take(span.column.saturating_sub(1))
+                    .map(|character| if character == '\t' { '\t' } else { ' ' })
+                    .collect::<String>();
+
+                output.push(format!("{} |", padding));
+                output.push(format!("{} | {}", span.line, line));
+                output.push(format!("{} | {}{}",
+                    padding, indent, "^".repeat(span.length.max(1))));
+            }
+        }
+
+        for note in &self.notes {
+            output.push(format!("{} = note: {}", padding, note));
+        }
+
+        output.join("\n")
+    }
+}
+
+impl Diagnostics {
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.entries.push(diagnostic);
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.entries.iter()
+            .filter(|d| d.severity == Severity::Error)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    // Prints every collected diagnostic to stderr.
+    pub fn report(&self, sources: &SourceMap) {
+        for diagnostic in &self.entries {
+            eprintln!("{}\n", diagnostic.render(sources));
+        }
+    }
+}
+
+impl SourceMap {
+    pub fn add_file(&mut self, path: String, content: String) {
+        self.files.push((path, content));
+    }
+
+    // Returns a line of a file, `line` starting at 1.
+    pub fn line(&self, path: &str, line: usize) -> Option<&str> {
+        self.files.iter()
+            .find(|(file_path, _)| file_path == path)
+            .and_then(|(_, content)| content.lines().nth(line.checked_sub(1)?))
+    }
+}
+
+impl SourceSpan {
+    pub fn new(file: &str, line: usize, column: usize, length: usize) -> Self {
+        Self { file: file.to_string(), line, column, length }
+    }
+
+    // Builds a span going from the start of `self` to the end of `other`.
+    // Spans over multiple lines are cut at the end of the first line.
+    pub fn to(&self, other: &SourceSpan) -> Self {
+        let length = if other.line == self.line {
+            (other.column + other.length).saturating_sub(self.column)
+        } else { self.length };
+
+        Self { length, ..self.clone() }
+    }
+}
+
+// Location of the span, as `[file]:[line]:[column]`.
+impl Display for SourceSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carets_line_up_with_tabs() {
+        let mut sources = SourceMap::default();
+        sources.add_file("test.tc".into(), 
+                         "module M\nstruct A\n\tstring Name [pattern(1)]\n"
+                         .into());
+        let diagnostic = Diagnostic::error(
+            DiagnosticKind::InvalidConstraintArguments { 
+                constraint: "pattern".into(), expected: "a string".into() },
+            Some(SourceSpan::new("test.tc", 3, 23, 1)));
+
+        assert_eq!(diagnostic.render(&sources).lines().skip(3)
+            .collect::<Vec<&str>>(), [
+                "3 | \tstring Name [pattern(1)]",
+                "  | \t                     ^"]);
+    }
+
+    #[test]
+    fn notes_follow_the_snippet() {
+        let diagnostic = Diagnostic::error(
+            DiagnosticKind::UnknownGenerator("py".into()), None)
+            .with_note("expected `rs`, `go` or `ts`");
+
+        assert_eq!(diagnostic.render(&SourceMap::default()), 
+                   "error[E0301]: unknown generator `py`\n \
+                    = note: expected `rs`, `go` or `ts`");
+    }
+}
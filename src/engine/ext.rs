@@ -1,19 +1,41 @@
-use heck::{ToLowerCamelCase, ToSnakeCase};
+use heck::{
+    ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 
 use crate::utils::file::try_write_bytes_to_file;
 use crate::{debug, warn};
+use super::ast::{Constraint, Literal, quote};
+use super::diagnostics::{Diagnostic, DiagnosticKind};
+use super::generator::{GeneratorOptions, TypeMapping, WideIntegers};
 use super::reusability::{ItemDeclarationDescriptor, ReusableDeclarations};
 use super::tokenizer::{TokenSet, TokenType, TokenParameter};
 
+// Indents a generated line by a level, empty lines being left empty.
+fn indent_line(line: String) -> String {
+    if line.is_empty() { line } else { format!("    {}", line) }
+}
 
 // ITEM DECLARATION DESCRIPTOR IMPLEMENTATIONS
 #[cfg(feature = "rust-gen")]
 use super::generator::RustReusability;
 #[cfg(feature = "rust-gen")]
 impl RustReusability for ItemDeclarationDescriptor {
+    fn produce_reusable_statement_from_struct_or_enum_token(
+        &self, from_module: &[String]) -> String {
+        // Paths start from the root of the generated file.
+        return format!("{}{}::{}", 
+                       "super::".repeat(from_module.len()),
+                       self.module_path.join("::"), 
+                       self.declaration_name);
+    }
+}
+
+#[cfg(feature = "go-gen")]
+use super::generator::GoReusability;
+#[cfg(feature = "go-gen")]
+impl GoReusability for ItemDeclarationDescriptor {
     fn produce_reusable_statement_from_struct_or_enum_token(&self) -> String {
-        return format!("super::{}::{}", 
-                       self.module_name, self.declaration_name);
+        return format!("{}{}", 
+                       self.module_path.concat(), self.declaration_name);
     }
 }
 
@@ -22,7 +44,8 @@ use super::generator::TSReusability;
 #[cfg(feature = "ts-gen")]
 impl TSReusability for ItemDeclarationDescriptor {
     fn produce_reusable_statement_from_struct_or_enum_token(&self) -> String {
-        return format!("{}.{}", self.module_name, self.declaration_name);
+        return format!("{}.{}", 
+                       self.module_path.join("."), self.declaration_name);
     }
 }
 
@@ -46,47 +69,153 @@ impl RustGen for TokenSet {
             TokenType::IntI32 => { "i32" }
             TokenType::IntU64 => { "u64" }
             TokenType::IntI64 => { "i64" }
+            TokenType::IntU128 => { "u128" }
+            TokenType::IntI128 => { "i128" }
+            TokenType::BigInt => { "num_bigint::BigInt" }
+            TokenType::Float32 => { "f32" }
+            TokenType::Float64 => { "f64" }
             TokenType::Structure => { "struct" }
             TokenType::Enumeration => { "enum" }
+            TokenType::Alias => { "type" }
+            TokenType::NewType => { "struct" }
+            TokenType::Constant => { "const" }
+            TokenType::Extern => { "type" }
+            // Field groups are expanded before generation.
+            TokenType::FieldGroup => { "" }
+            TokenType::Map => { "HashMap" }
+            TokenType::Vector => { "Vec" }
+            TokenType::Array => { "array" }
+            TokenType::Set => { "HashSet" }
+            TokenType::Tuple => { "tuple" }
+            TokenType::Bytes | TokenType::Uuid | TokenType::DateTime
+                | TokenType::Date | TokenType::Duration 
+                | TokenType::Decimal => {
+                return <TokenSet as RustGen>::map_well_known_type(
+                    &token.token_type, &GeneratorOptions::default()).target }
             TokenType::Custom => { 
                 return token.clone().custom_token_type.unwrap(); }
         }.into()
     }
 
+    fn known_annotations() -> &'static [&'static str] {
+        &["rename", "rename_all", "deprecated"]
+    }
+
+    fn map_well_known_type(token_type: &TokenType, options: &GeneratorOptions)
+        -> TypeMapping {
+        let keyword = token_type.well_known_keyword().unwrap_or_default();
+
+        if let Some(mapping) = options.type_mappings.get(keyword) {
+            return mapping.clone();
+        }
+
+        // Types from crates are written with their full path.
+        match token_type {
+            TokenType::Bytes => TypeMapping::new("Vec<u8>", &[]),
+            TokenType::Uuid => TypeMapping::new("uuid::Uuid", &[]),
+            TokenType::DateTime => 
+                TypeMapping::new("chrono::DateTime<chrono::Utc>", &[]),
+            TokenType::Date => TypeMapping::new("chrono::NaiveDate", &[]),
+            TokenType::Duration => 
+                TypeMapping::new("std::time::Duration", &[]),
+            _ => TypeMapping::new("rust_decimal::Decimal", &[])
+        }
+    }
+
     fn build_type_declaration(
-            token: &TokenSet, reusability: &ReusableDeclarations) -> String {
-        let mut output_type = format!("{}", <TokenSet as RustGen>::
-                                  generate_keyword_from_token_type(token));
+            token: &TokenSet, 
+            reusability: &ReusableDeclarations,
+            module_path: &[String],
+            options: &GeneratorOptions) -> String {
+       let mut output_type = <TokenSet as RustGen>::build_type(
+           token, reusability, module_path, options);
+
+       // Fields that can be both absent and null are doubly optional.
+       if token.is_optional_field() && token.is_nullable_field() {
+           output_type = format!("Option<{}>", output_type);
+       }
+
+       return format!("pub {}: {}", 
+                      token.token_name.to_snake_case(), output_type)
+    }
+
+    fn build_type(
+            token: &TokenSet, 
+            reusability: &ReusableDeclarations,
+            module_path: &[String],
+            options: &GeneratorOptions) -> String {
+        let mut output_type = <TokenSet as RustGen>::
+            generate_keyword_from_token_type(token);
+
+        if token.token_type.is_well_known() {
+            output_type = <TokenSet as RustGen>::map_well_known_type(
+                &token.token_type, options).target;
+        }
+
+        // Maps are made of their key and value types.
+        if token.token_type == TokenType::Map {
+            output_type = format!("std::collections::{}<{}, {}>",
+                if options.btree_map { "BTreeMap" } else { "HashMap" },
+                <TokenSet as RustGen>::build_type(
+                    &token.type_arguments[0], reusability, module_path,
+                    options),
+                <TokenSet as RustGen>::build_type(
+                    &token.type_arguments[1], reusability, module_path,
+                    options));
+        }
+
+        // Collections are made of their item type, sets following the
+        // ordering of maps.
+        if matches!(token.token_type, 
+                    TokenType::Vector | TokenType::Array | TokenType::Set) {
+            let item = <TokenSet as RustGen>::build_type(
+                &token.type_arguments[0], reusability, module_path, options);
+
+            output_type = match token.token_type {
+                TokenType::Vector => format!("Vec<{}>", item),
+                TokenType::Array => 
+                    format!("[{}; {}]", item, token.array_length.unwrap()),
+                _ => format!("std::collections::{}<{}>",
+                    if options.btree_map { "BTreeSet" } else { "HashSet" },
+                    item)
+            };
+        }
+
+        // Tuples are made of the types of their items.
+        if token.token_type == TokenType::Tuple {
+            output_type = format!("({})", token.type_arguments.iter()
+                .map(|item| <TokenSet as RustGen>::build_type(
+                    item, reusability, module_path, options))
+                .collect::<Vec<String>>().join(", "));
+        }
 
         if token.parameters.contains(&TokenParameter::LocalType) {
-            let reusable_data = reusability.
-                    find_declaration_descriptor_with_declaration_name( 
-                        token.custom_token_type.clone().unwrap());
+            // Local types are qualified once resolved.
+            let reusable_data = reusability.resolve_declaration(
+                token.custom_token_type.as_ref().unwrap(), &[]).ok();
 
             debug!("Found a token with the `local` parameter.");
 
-            if reusable_data.is_some() {
+            if let Some(reusable_data) = reusable_data {
                 output_type = <ItemDeclarationDescriptor as RustReusability>
                     ::produce_reusable_statement_from_struct_or_enum_token(
-                        reusable_data.unwrap());
+                        reusable_data, module_path);
             } 
         }
 
-        if token.parameters.contains(&TokenParameter::Optional) {
-            output_type = format!("Option<{}>", output_type);
+        // Generic types are followed by their type arguments.
+        if token.token_type == TokenType::Custom && 
+            !token.type_arguments.is_empty() {
+            output_type = format!("{}<{}>", output_type, 
+                token.type_arguments.iter()
+                    .map(|argument| <TokenSet as RustGen>::build_type(
+                        argument, reusability, module_path, options))
+                    .collect::<Vec<String>>().join(", "));
         }
 
-        if token.parameters.contains(&TokenParameter::Floated) {
-            match token.token_type {
-                TokenType::IntU32 | TokenType::IntI32| TokenType::IntU64 
-                    | TokenType::IntI64 => {
-                        output_type = output_type.replace("u", "f")
-                            .replace("i", "f");
-                    }
-                _ => { 
-                    warn!("Rust: Floated applies only to [u|i][32|64] types.") 
-                } 
-            }
+        if token.parameters.contains(&TokenParameter::Optional) ||
+            token.parameters.contains(&TokenParameter::Nullable) {
+            output_type = format!("Option<{}>", output_type);
         }
 
        if token.parameters.contains(&TokenParameter::Pointer) ||
@@ -100,57 +229,681 @@ impl RustGen for TokenSet {
             output_type = format!("Vec<{}>", output_type);
        }
 
-       return format!("pub {}: {}", 
-                      token.token_name.to_snake_case(), output_type)
+       return output_type
+    }
+
+    fn build_doc_comment(token: &TokenSet, indent: &str) -> Vec<String> {
+        let mut output: Vec<String> = token.docs.iter()
+            .map(|line| format!("{}/// {}", indent, line).trim_end().into())
+            .collect();
+
+        if let Some(message) = &token.deprecation {
+            output.push(format!("{}#[deprecated(note = {:?})]", 
+                                indent, message));
+        }
+        return output;
+    }
+
+    fn build_serde_as_type(
+        token: &TokenSet,
+        reusability: &ReusableDeclarations,
+        module_path: &[String],
+        options: &GeneratorOptions) -> Option<String> {
+        let keyword = token.token_type.well_known_keyword().unwrap_or_default();
+        let items = token.type_arguments.iter()
+            .map(|argument| <TokenSet as RustGen>::build_serde_as_type(
+                argument, reusability, module_path, options))
+            .collect::<Vec<Option<String>>>();
+        let adapted_items = items.iter().any(Option::is_some);
+        let items = items.into_iter()
+            .map(|item| item.unwrap_or("_".into()))
+            .collect::<Vec<String>>();
+
+        // Bytes are base64 strings and durations integer nanoseconds, unless
+        // they're mapped to another type.
+        let mut output = match token.token_type {
+            _ if options.type_mappings.contains_key(keyword) => None,
+            TokenType::Bytes => Some("serde_with::base64::Base64".into()),
+            TokenType::Duration => 
+                Some("serde_with::DurationNanoSeconds<u64>".into()),
+            _ if !adapted_items => None,
+            TokenType::Vector => Some(format!("Vec<{}>", items[0])),
+            TokenType::Array => Some(format!(
+                "[{}; {}]", items[0], token.array_length.unwrap())),
+            TokenType::Set => Some(format!("std::collections::{}<{}>",
+                if options.btree_map { "BTreeSet" } else { "HashSet" },
+                items[0])),
+            TokenType::Map => Some(format!("std::collections::{}<{}, {}>",
+                if options.btree_map { "BTreeMap" } else { "HashMap" },
+                items[0], items[1])),
+            TokenType::Tuple => Some(format!("({})", items.join(", "))),
+            _ => None
+        };
+
+        // Aliases are serialized as the type they stand for.
+        if token.parameters.contains(&TokenParameter::LocalType) {
+            output = reusability.resolve_declaration(
+                token.custom_token_type.as_ref().unwrap(), module_path).ok()
+                .filter(|declaration| 
+                        declaration.declaration_type == TokenType::Alias)
+                .and_then(|declaration| declaration.aliased_type.as_ref()
+                    .and_then(|aliased_type| <TokenSet as RustGen>::
+                        build_serde_as_type(aliased_type, reusability, 
+                                            &declaration.module_path, 
+                                            options)));
+        }
+
+        if token.parameters.contains(&TokenParameter::Optional) ||
+            token.parameters.contains(&TokenParameter::Nullable) {
+            output = output.map(|output| format!("Option<{}>", output));
+        }
+        if token.parameters.contains(&TokenParameter::Vector) {
+            output = output.map(|output| format!("Vec<{}>", output));
+        }
+
+        return output;
+    }
+
+    fn build_attributes(
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Vec<String> {
+        let mut output = vec![];
+
+        // Type aliases, extern types and constants don't need attributes.
+        if token.token_type == TokenType::Alias || 
+            token.token_type == TokenType::Extern ||
+            token.token_type == TokenType::Constant { return output; }
+
+        // Enums without data can be used as map keys, and so can newtypes
+        // wrapping strings or integers.
+        if token.token_type == TokenType::Enumeration && 
+            !token.is_tagged_union() {
+            output.push(format!("#[derive(Clone, Copy, {}PartialEq, Eq, \
+                                Hash, PartialOrd, Ord)]", 
+                                if token.has_unit_first_constant() { 
+                                    "Default, " 
+                                } else { "" }));
+        } else if token.token_type == TokenType::NewType && 
+            token.childs[0].parameters.is_empty() &&
+            matches!(token.childs[0].token_type, 
+                     TokenType::String | TokenType::IntU8 | TokenType::IntU16 
+                     | TokenType::IntU32 | TokenType::IntU64 
+                     | TokenType::IntI8 | TokenType::IntI16 
+                     | TokenType::IntI32 | TokenType::IntI64) {
+            output.push("#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, \
+                        Ord)]".into());
+        }
+
+        // Enums valued with integers are serialized as their value, which
+        // `serde_repr` takes care of.
+        if token.token_type == TokenType::Enumeration && 
+            token.has_integer_values() {
+            if options.serde {
+                output.push("#[derive(serde_repr::Serialize_repr, \
+                            serde_repr::Deserialize_repr)]".into());
+            }
+            output.push("#[repr(i64)]".into());
+        } else if options.serde {
+            // `serde_as` has to come before the derived traits.
+            let adapted = token.childs.iter()
+                .flat_map(|child| 
+                    std::iter::once(child).chain(child.childs.iter()))
+                .any(|field| <TokenSet as RustGen>::build_serde_as_type(
+                    field, reusability, &[], options).is_some());
+
+            if adapted {
+                output.push("#[serde_with::serde_as]".into());
+            }
+            output.push(
+                "#[derive(serde::Serialize, serde::Deserialize)]".into());
+        }
+
+        if token.is_tagged_union() && options.serde {
+            output.push(format!("#[serde(tag = {:?})]", options.tag_field));
+        }
+        if token.is_tagged_union() && token.has_unit_first_constant() {
+            output.push("#[derive(Default)]".into());
+        }
+
+        // Newtypes are serialized as the type they wrap.
+        if token.token_type == TokenType::NewType && options.serde {
+            output.push("#[serde(transparent)]".into());
+        }
+
+        return output;
+    }
+
+    fn build_default_implementation(
+        token: &TokenSet,
+        reusability: &ReusableDeclarations,
+        module_path: &[String],
+        implements_default: bool,
+        options: &GeneratorOptions) -> Vec<String> {
+        let mut output = vec![];
+
+        // Nothing would call the functions without an implementation.
+        if !implements_default && !options.serde { return output; }
+
+        // Default values are returned by functions so that serde can use
+        // them as well.
+        for field in token.childs.iter().filter(|field| field.value.is_some()) {
+            let mut value = field.value_code("rs");
+
+            if field.token_type == TokenType::String {
+                value = format!("{}.into()", value);
+            }
+            if field.parameters.contains(&TokenParameter::Optional) {
+                value = format!("Some({})", value);
+            }
+
+            output.extend([
+                format!("    fn default_{}_{}() -> {} {{", 
+                        token.token_name.to_snake_case(),
+                        field.token_name.to_snake_case(),
+                        <TokenSet as RustGen>::build_type(
+                            field, reusability, module_path, options)),
+                format!("        {}", value),
+                "    }".into()]);
+        }
+        if !implements_default { return output; }
+
+        // Type parameters are required to have defaults as well.
+        let (bounds, type_parameters) = if token.type_parameters.is_empty() {
+            ("".into(), "".into())
+        } else {
+            (format!("<{}>", token.type_parameters.iter()
+                .map(|parameter| format!("{}: Default", parameter))
+                .collect::<Vec<String>>().join(", ")),
+             format!("<{}>", token.type_parameters.join(", ")))
+        };
+
+        output.extend([
+            format!("    impl{} Default for {}{} {{", 
+                    bounds, token.token_name, type_parameters),
+            "        fn default() -> Self {".into(),
+            "            Self {".into()]);
+        output.extend(token.childs.iter().map(|field| 
+            format!("                {}: {},", 
+                    field.token_name.to_snake_case(),
+                    if field.value.is_some() { 
+                        format!("default_{}_{}()", 
+                                token.token_name.to_snake_case(), 
+                                field.token_name.to_snake_case())
+                    } else { "Default::default()".into() })));
+        output.extend([
+            "            }".into(),
+            "        }".into(),
+            "    }".into()]);
+
+        return output;
+    }
+
+    fn build_validate_method(token: &TokenSet, module_path: &[String])
+        -> Vec<String> {
+        let type_parameters = if token.type_parameters.is_empty() { 
+            "".into() 
+        } else { 
+            format!("<{}>", token.type_parameters.join(", ")) 
+        };
+        // Validation errors are declared at the root of the generated file.
+        let error_type = format!("{}ValidationError", 
+                                 "super::".repeat(module_path.len()));
+        let mut output = vec![
+            format!("    impl{} {}{} {{", 
+                    type_parameters, token.token_name, type_parameters),
+            "        /// Checks the constraints of every field, returning the \
+            ones that aren't satisfied.".into(),
+            format!("        pub fn validate(&self) -> \
+                    Result<(), Vec<{}>> {{", error_type),
+            "            let mut errors = vec![];".into()];
+        // Patterns are compiled once, in statics declared before the method.
+        let mut patterns = vec![];
+
+        for field in token.childs.iter()
+            .filter(|field| !field.constraints.is_empty()) {
+            let field_name = field.token_name.to_snake_case();
+            let is_text = field.token_type == TokenType::String && 
+                !field.parameters.contains(&TokenParameter::Vector);
+            // Optional and nullable values are only checked when present.
+            // Vectors of optional values are always present.
+            let is_optional = field.is_optional_field() || 
+                field.is_nullable_field();
+            let (value, place, indent) = if is_optional {
+                output.push(format!("            if let {} = &self.{} {{",
+                                    if field.is_optional_field() && 
+                                        field.is_nullable_field() { 
+                                        "Some(Some(value))" 
+                                    } else { "Some(value)" },
+                                    field_name));
+                ("value".to_string(), "*value".to_string(), "    ")
+            } else { 
+                (format!("self.{}", field_name), 
+                 format!("self.{}", field_name), "") 
+            };
+
+            let mut field_patterns = 0;
+
+            for constraint in &field.constraints {
+                let condition = match constraint {
+                    Constraint::Min(min) => 
+                        format!("{} < {}", place, min.to_code("rs")),
+                    Constraint::Max(max) => 
+                        format!("{} > {}", place, max.to_code("rs")),
+                    Constraint::Length(min, max) => 
+                        format!("!({}..={}).contains(&{}.{})", min, max, value,
+                                if is_text { "chars().count()" } 
+                                else { "len()" }),
+                    Constraint::Pattern(pattern) => {
+                        let static_name = format!("{}_{}_PATTERN{}",
+                            token.token_name.to_shouty_snake_case(),
+                            field.token_name.to_shouty_snake_case(),
+                            if field_patterns == 0 { "".into() } 
+                            else { format!("_{}", field_patterns) });
+
+                        field_patterns += 1;
+
+                        patterns.extend([
+                            format!("    static {}: std::sync::LazyLock<\
+                                    regex::Regex> =", static_name),
+                            format!("        std::sync::LazyLock::new(|| \
+                                    regex::Regex::new({:?}).unwrap());", 
+                                    pattern)]);
+                        format!("!{}.is_match(&{})", static_name, place)
+                    }
+                    Constraint::NonEmpty => format!("{}.is_empty()", value)
+                };
+
+                output.extend([
+                    format!("            {}if {} {{", indent, condition),
+                    format!("            {}    errors.push({} {{", 
+                            indent, error_type),
+                    format!("            {}        field: {:?}, \
+                            constraint: {:?} }});", 
+                            indent, field_name, constraint.describe()),
+                    format!("            {}}}", indent)]);
+            }
+
+            if is_optional { output.push("            }".into()); }
+        }
+
+        output.extend([
+            "            if errors.is_empty() { Ok(()) } else { Err(errors) }"
+                .into(),
+            "        }".into(),
+            "    }".into()]);
+
+        return patterns.into_iter().chain(output).collect();
+    }
+
+    fn find_default_declarations(source: &[TokenSet]) -> Vec<String> {
+        let declarations = source.iter()
+            .flat_map(|module| module.declarations())
+            .map(|(module_path, declaration)| (
+                format!("{}.{}", module_path.join("."), declaration.token_name),
+                declaration.with_inherited_fields()))
+            .collect::<Vec<(String, TokenSet)>>();
+        let mut output = declarations.iter()
+            .filter(|(_, declaration)| declaration.has_unit_first_constant())
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<String>>();
+
+        // Structs and aliases are added once the types they are made of are
+        // known to implement it, until no more are found.
+        loop {
+            let found = declarations.iter()
+                .filter(|(name, declaration)| !output.contains(name) && 
+                    match declaration.token_type {
+                        TokenType::Structure => declaration.childs.iter()
+                            .filter(|field| field.value.is_none())
+                            .all(|field| <TokenSet as RustGen>::
+                                 implements_default(field, &output)),
+                        TokenType::Alias => <TokenSet as RustGen>::
+                            implements_default(
+                                &declaration.childs[0], &output),
+                        _ => false
+                    })
+                .map(|(name, _)| name.clone())
+                .collect::<Vec<String>>();
+
+            if found.is_empty() { return output; }
+            output.extend(found);
+        }
+    }
+
+    fn implements_default(token: &TokenSet, defaults: &[String]) -> bool {
+        if token.parameters.contains(&TokenParameter::Pointer) ||
+            token.parameters.contains(&TokenParameter::Reference) {
+            return false;
+        }
+        if token.parameters.contains(&TokenParameter::Optional) ||
+            token.parameters.contains(&TokenParameter::Nullable) ||
+            token.parameters.contains(&TokenParameter::Vector) {
+            return true;
+        }
+
+        let arguments = token.type_arguments.iter()
+            .all(|argument| <TokenSet as RustGen>::
+                 implements_default(argument, defaults));
+
+        // The standard library implements it for arrays of up to 32 items
+        // and tuples of up to 12 items. Types declared outside of TypeCode
+        // are expected to implement it.
+        match token.token_type {
+            TokenType::Map | TokenType::Vector | TokenType::Set => true,
+            TokenType::Array => 
+                token.array_length.is_some_and(|length| length <= 32) && 
+                arguments,
+            TokenType::Tuple => token.type_arguments.len() <= 12 && arguments,
+            TokenType::Custom if token.parameters
+                .contains(&TokenParameter::LocalType) => 
+                defaults.contains(token.custom_token_type.as_ref().unwrap()) &&
+                arguments,
+            TokenType::Custom => arguments,
+            _ => true
+        }
+    }
+
+    fn build_module(
+        token: &TokenSet,
+        parent_path: &[String],
+        reusability: &ReusableDeclarations,
+        defaults: &[String],
+        options: &GeneratorOptions) -> Result<Vec<String>, Diagnostic> {
+        let mut module_path = parent_path.to_vec();
+        module_path.push(token.token_name.clone());
+
+        let mut content_lines = <TokenSet as RustGen>::build_doc_comment(
+            token, "");
+        content_lines.push(format!("pub mod {} {{", token.token_name));
+
+        // Items used by extern and well-known types are only imported in
+        // their module.
+        let mut imports = token.childs.iter()
+            .filter(|child| child.token_type == TokenType::Extern)
+            .flat_map(|child| child.extern_imports("rs"))
+            .collect::<Vec<String>>();
+        imports.extend(token.childs.iter()
+            .filter(|child| child.token_type != TokenType::Module)
+            .flat_map(|child| child.used_types())
+            .filter(|token_type| token_type.is_well_known())
+            .flat_map(|token_type| <TokenSet as RustGen>::
+                map_well_known_type(&token_type, options).imports));
+        imports.sort();
+        imports.dedup();
+        content_lines.extend(imports.iter()
+            .map(|import| format!("    use {};", import)));
+
+        // Loops through the Module's childs.
+        for secondary_item /* Such as struct or enum. */ in &token.childs {
+            // Nested modules are indented within their parent.
+            if secondary_item.token_type == TokenType::Module {
+                content_lines.extend(<TokenSet as RustGen>::build_module(
+                        secondary_item, &module_path, reusability, defaults, 
+                        options)?
+                    .into_iter().map(indent_line));
+                continue;
+            }
+            if !secondary_item.is_declaration() { return 
+                    Err(Diagnostic::error(
+                        DiagnosticKind::FieldOutsideDeclaration,
+                        Some(secondary_item.span.clone()))) }
+
+            // Structs are declared along with the fields of the structs they
+            // extend.
+            let secondary_item = &secondary_item.with_inherited_fields();
+
+            content_lines.extend(<TokenSet as RustGen>::build_doc_comment(
+                    secondary_item, "    "));
+            content_lines.extend(<TokenSet as RustGen>::build_attributes(
+                    secondary_item, reusability, options).iter()
+                    .map(|attribute| format!("    {}", attribute)));
+
+            // Structs without default values derive `Default` when they can,
+            // the other ones implementing it themselves.
+            let implements_default = defaults.contains(&format!(
+                "{}.{}", module_path.join("."), secondary_item.token_name));
+
+            if secondary_item.token_type == TokenType::Structure && 
+                implements_default && !secondary_item.has_default_values() {
+                content_lines.push("    #[derive(Default)]".into());
+            }
+
+            // Constants of strings are string slices, as `String`s can't
+            // be built at compile time.
+            if secondary_item.token_type == TokenType::Constant {
+                let const_type = &secondary_item.childs[0];
+
+                content_lines.push(format!("    pub const {}: {} = {};", 
+                    secondary_item.token_name.to_shouty_snake_case(),
+                    if const_type.token_type == TokenType::String { 
+                        "&str".into() 
+                    } else { 
+                        <TokenSet as RustGen>::build_type(
+                            const_type, reusability, &module_path, options)
+                    },
+                    secondary_item.value_code("rs")));
+                continue;
+            }
+
+            // Extern types are aliases of the type they are mapped to.
+            if secondary_item.token_type == TokenType::Extern {
+                content_lines.push(format!("    pub type {} = {};", 
+                    secondary_item.token_name,
+                    secondary_item.extern_mapping("rs")?
+                        .custom_token_type.as_ref().unwrap()));
+                continue;
+            }
+
+            // Aliases and newtypes are declared on a single line.
+            if secondary_item.token_type == TokenType::Alias ||
+                secondary_item.token_type == TokenType::NewType {
+                let target = <TokenSet as RustGen>::build_type(
+                    &secondary_item.childs[0], reusability, &module_path,
+                    options);
+
+                content_lines.push(
+                    if secondary_item.token_type == TokenType::Alias {
+                        format!("    pub type {} = {};", 
+                                secondary_item.token_name, target)
+                    } else {
+                        format!("    pub struct {}({}pub {});",
+                                secondary_item.token_name, 
+                                <TokenSet as RustGen>::build_serde_as_type(
+                                    &secondary_item.childs[0], reusability, 
+                                    &[], options)
+                                    .filter(|_| options.serde)
+                                    .map(|serde_as| format!(
+                                        "#[serde_as(as = {:?})] ", serde_as))
+                                    .unwrap_or_default(),
+                                target)
+                    });
+                continue;
+            }
+
+            content_lines.push(format!("    pub {} {}{} {{", 
+                <TokenSet as RustGen>::
+                generate_keyword_from_token_type(secondary_item),
+                secondary_item.token_name,
+                if secondary_item.type_parameters.is_empty() { "".into() }
+                else { format!("<{}>", 
+                               secondary_item.type_parameters.join(", ")) }
+                ));
+
+            // Loop through the inner types of a struct/enum.
+            secondary_item.childs.iter().enumerate().for_each(|(pos, item)| {
+                let colon = pos < secondary_item.childs.len();
+
+                content_lines.extend(<TokenSet as RustGen>::
+                    build_doc_comment(item, "        "));
+                // The first constant of an enum is its default value.
+                if pos == 0 && secondary_item.has_unit_first_constant() {
+                    content_lines.push("        #[default]".into());
+                }
+                if secondary_item.token_type == TokenType::Structure {
+                    if item.value.is_some() && options.serde {
+                        content_lines.push(format!(
+                            "        #[serde(default = \"default_{}_{}\")]",
+                            secondary_item.token_name.to_snake_case(),
+                            item.token_name.to_snake_case()));
+                    }
+                    // Names matching the Rust one are left as is.
+                    let rust_name = item.token_name.to_snake_case();
+
+                    if let Some(name) = item.serialized_name.as_ref()
+                        .filter(|name| **name != rust_name && options.serde) {
+                        content_lines.push(format!(
+                            "        #[serde(rename = {:?})]", name));
+                    }
+                    // Adapted values can't be told apart from null ones when
+                    // absent, `serde_as` taking the place of `with`.
+                    let serde_as = <TokenSet as RustGen>::build_serde_as_type(
+                        item, reusability, &[], options)
+                        .filter(|_| options.serde)
+                        .map(|serde_as| if item.is_optional_field() && 
+                             item.is_nullable_field() { 
+                            format!("Option<{}>", serde_as) 
+                        } else { serde_as });
+
+                    if let Some(serde_as) = &serde_as {
+                        content_lines.push(format!(
+                            "        #[serde_as(as = {:?})]", serde_as));
+                    }
+                    // Absent values are left out, and told apart from null
+                    // ones by `serde_with` when both are allowed.
+                    if item.is_optional_field() && options.serde {
+                        content_lines.push(if item.is_nullable_field() && 
+                            serde_as.is_none() {
+                            "        #[serde(default, skip_serializing_if = \
+                            \"Option::is_none\", with = \
+                            \"::serde_with::rust::double_option\")]".into()
+                        } else {
+                            "        #[serde(skip_serializing_if = \
+                            \"Option::is_none\")]".into()
+                        });
+                    }
+                    content_lines.push(format!("        {}{}",
+                        <TokenSet as RustGen>::build_type_declaration(
+                            item, reusability, &module_path, options),
+                        if colon { "," } else { "" }));
+                } else {
+                    // Integer values are discriminants, while string
+                    // values only exist once serialized. Tagged unions
+                    // constants are followed by their payload.
+                    let discriminant = match &item.value {
+                        Some(value @ Literal::Integer(_)) => 
+                            format!(" = {}", value.to_code("rs")),
+                        Some(value @ Literal::Text(_)) if options.serde => {
+                            content_lines.push(format!(
+                                "        #[serde(rename = {})]", 
+                                value.to_code("rs")));
+                            "".into()
+                        }
+                        _ if item.payload.is_some() => format!("({})",
+                            <TokenSet as RustGen>::build_type(
+                                item.payload.as_ref().unwrap(), 
+                                reusability, &module_path, options)),
+                        _ if !item.childs.is_empty() => format!(" {{ {} }}",
+                            item.childs.iter().map(|field| {
+                                let mut attributes = String::new();
+                                let rust_name =
+                                    field.token_name.to_snake_case();
+
+                                if let Some(name) = field.serialized_name
+                                    .as_ref().filter(|name| 
+                                        **name != rust_name && options.serde) {
+                                    attributes += &format!(
+                                        "#[serde(rename = {:?})] ", name);
+                                }
+                                if let Some(serde_as) = <TokenSet as RustGen>::
+                                    build_serde_as_type(
+                                        field, reusability, &[], options)
+                                    .filter(|_| options.serde) {
+                                    attributes += &format!(
+                                        "#[serde_as(as = {:?})] ", serde_as);
+                                }
+                                format!("{}{}: {}", attributes, rust_name,
+                                    <TokenSet as RustGen>::build_type(
+                                        field, reusability, &module_path, 
+                                        options))
+                            })
+                            .collect::<Vec<String>>().join(", ")),
+                        _ => "".into()
+                    };
+
+                    content_lines.push(format!("        {}{}{}",
+                        item.custom_token_type.as_ref().unwrap(),
+                        discriminant,
+                        if colon { "," } else { "" }));
+                }
+            });
+            content_lines.push("    }".into());
+
+            if secondary_item.has_default_values() {
+                content_lines.extend(<TokenSet as RustGen>::
+                    build_default_implementation(
+                        secondary_item, reusability, &module_path, 
+                        implements_default, options));
+            }
+
+            if secondary_item.has_constraints() {
+                content_lines.extend(<TokenSet as RustGen>::
+                    build_validate_method(secondary_item, &module_path));
+            }
+        }
+        content_lines.push("}".into());
+
+        return Ok(content_lines);
     }
 
     fn produce_rs_build_in_single_file(
             source: Vec<TokenSet>,
             reusability: ReusableDeclarations,
-            output_path: String) -> Result<(), String> {
+            output_path: String,
+            options: &GeneratorOptions) -> Result<(), Diagnostic> {
         // Content is generated line by line and is put here before being 
         // joined at save time.
         let mut content_lines: Vec<String> = vec![];
+        let has_validators = source.iter()
+            .any(|module| module.declarations().iter()
+                 .any(|(_, declaration)| declaration.has_constraints()));
+
+        // Deprecated items are still used by the generated code, only their
+        // uses outside of it being reported.
+        let has_deprecations = source.iter()
+            .any(|module| !module.deprecated_in_tree("").is_empty());
+        let defaults = <TokenSet as RustGen>::find_default_declarations(
+            &source);
 
         for root_item in source {
             // When generating Rust code, a root_item always has to be a
             // module.
             if root_item.token_type != TokenType::Module { return 
-                Err("Modules have to be declared before anything.".into()) }
+                Err(Diagnostic::error(
+                    DiagnosticKind::DeclarationOutsideModule(
+                        root_item.token_type), 
+                    Some(root_item.span))) }
 
-            content_lines.push(format!("pub mod {} {{", root_item.token_name));
-            
-            // Loops through the Module's childs.
-            for secondary_item /* Such as struct or enum. */ in 
-                root_item.childs {
-                if secondary_item.token_type != TokenType::Structure &&
-                    secondary_item.token_type != TokenType::Enumeration { return 
-                        Err("Structs/Enums have to be defined after modules."
-                            .into()) }
-
-                content_lines.push(format!("    pub {} {} {{", 
-                    <TokenSet as RustGen>::
-                    generate_keyword_from_token_type(&secondary_item),
-                    secondary_item.token_name));
-
-                // Loop through the inner types of a struct/enum.
-                secondary_item.childs.iter().enumerate().for_each(|(pos, item)| {
-                    let colon = pos < secondary_item.childs.len();
-
-                    if secondary_item.token_type == TokenType::Structure {
-                        content_lines.push(format!("        {}{}",
-                            <TokenSet as RustGen>::build_type_declaration(
-                                item, &reusability),
-                            if colon { "," } else { "" }));
-                    } else {
-                        content_lines.push(format!("        {}{}",
-                            item.custom_token_type.as_ref().unwrap(),
-                            if colon { "," } else { "" }));
-                    }
-                });
-                content_lines.push("    }".into());
+            if has_deprecations {
+                content_lines.push("#[allow(deprecated)]".into());
             }
-            content_lines.push("}".into());
+            content_lines.extend(<TokenSet as RustGen>::build_module(
+                    &root_item, &[], &reusability, &defaults, options)?);
+        }
+
+        // Validation errors are shared by every module.
+        if has_validators {
+            content_lines.extend([
+                "/// A field value that doesn't satisfy one of its constraints."
+                    .into(),
+                "#[derive(Debug, Clone, PartialEq)]".into(),
+                "pub struct ValidationError {".into(),
+                "    /// Name of the field.".into(),
+                "    pub field: &'static str,".into(),
+                "    /// Constraint as written in TypeCode, such as `min(1)`."
+                    .into(),
+                "    pub constraint: &'static str".into(),
+                "}".to_string()]);
         }
 
         let content_lines = content_lines.join("\n");
@@ -158,6 +911,8 @@ impl RustGen for TokenSet {
         debug!("Generated content:\n{}", content_lines);
 
         try_write_bytes_to_file(output_path, content_lines.as_bytes())
+            .map_err(|reason| Diagnostic::error(
+                DiagnosticKind::OutputWriteFailed(reason), None))
     }
 }
 
@@ -180,49 +935,127 @@ impl GoGen for TokenSet {
             TokenType::IntI16 => { "int16" }
             TokenType::IntI32 => { "int32" }
             TokenType::IntI64 => { "int64" }
+            TokenType::IntU128 | TokenType::IntI128 
+                | TokenType::BigInt => { "*big.Int" }
+            TokenType::Float32 => { "float32" }
+            TokenType::Float64 => { "float64" }
+            TokenType::Map => { "map" }
+            TokenType::Bytes | TokenType::Uuid | TokenType::DateTime
+                | TokenType::Date | TokenType::Duration 
+                | TokenType::Decimal => {
+                return <TokenSet as GoGen>::map_well_known_type(
+                    &token.token_type, &GeneratorOptions::default()).target }
             TokenType::Custom => { return token.clone().
                 custom_token_type.unwrap() }
             _ => { "" }
         }.into()
     }
 
+    fn known_annotations() -> &'static [&'static str] {
+        &["rename", "rename_all", "deprecated"]
+    }
+
+    fn map_well_known_type(token_type: &TokenType, options: &GeneratorOptions)
+        -> TypeMapping {
+        let keyword = token_type.well_known_keyword().unwrap_or_default();
+
+        if let Some(mapping) = options.type_mappings.get(keyword) {
+            return mapping.clone();
+        }
+
+        // Types without a standard library equivalent are strings, as 
+        // they're serialized by the other generators.
+        match token_type {
+            TokenType::Bytes => TypeMapping::new("[]byte", &[]),
+            TokenType::DateTime => TypeMapping::new("time.Time", &["time"]),
+            TokenType::Duration => 
+                TypeMapping::new("time.Duration", &["time"]),
+            _ => TypeMapping::new("string", &[])
+        }
+    }
+
     fn build_type_declaration(
-        token: &TokenSet, reusability: &ReusableDeclarations) -> String {
-        // Due to enums not existing as a proper thing, a custom type pointing
-        // to an enum cannot exist. It's thus mapped as a `string`. 
-        if token.parameters.contains(&TokenParameter::LocalType) {
-            // Determines if the custom token type is present inside the enum
-            // flatten reusability list, if it's present it means the custom type
-            // maps to an enum. 
-        
-            let search_output = 
-                reusability.find_declaration_descriptor_with_declaration_name(
-                    token.custom_token_type.clone().unwrap());
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> String {
+       let mut output_type = <TokenSet as GoGen>::
+           generate_keyword_from_token_type(token);
 
-            if search_output.is_some() { return "string".into() }
+        if token.token_type.is_well_known() {
+            output_type = <TokenSet as GoGen>::map_well_known_type(
+                &token.token_type, options).target;
         }
 
+        if token.token_type.is_big_integer() && 
+            options.wide_integers == WideIntegers::String {
+            output_type = "string".into();
+        }
 
-       let mut output_type = format!("{}", <TokenSet as GoGen>::
-                                  generate_keyword_from_token_type(token));
+        // Maps are made of their key and value types.
+        if token.token_type == TokenType::Map {
+            output_type = format!("map[{}]{}",
+                <TokenSet as GoGen>::build_type_declaration(
+                    &token.type_arguments[0], reusability, options),
+                <TokenSet as GoGen>::build_type_declaration(
+                    &token.type_arguments[1], reusability, options));
+        }
 
-       if token.parameters.contains(&TokenParameter::Optional) {
-            output_type = format!("*{}", output_type);
-       }
+        // Collections are made of their item type, sets being maps of 
+        // empty values.
+        if matches!(token.token_type, 
+                    TokenType::Vector | TokenType::Array | TokenType::Set) {
+            let item = <TokenSet as GoGen>::build_type_declaration(
+                &token.type_arguments[0], reusability, options);
 
-        if token.parameters.contains(&TokenParameter::Floated) {
-            match token.token_type {
-                TokenType::IntU32 | TokenType::IntI32| TokenType::IntU64 
-                    | TokenType::IntI64 => {
-                        output_type = output_type.replace("u", "")
-                            .replace("int", "float");
-                    }
-                _ => { 
-                    warn!("Go: Floated applies only to [u|i][32|64] types.") 
-                } 
+            output_type = match token.token_type {
+                TokenType::Vector => format!("[]{}", item),
+                TokenType::Array => 
+                    format!("[{}]{}", token.array_length.unwrap(), item),
+                _ => format!("map[{}]struct{{}}", item)
+            };
+        }
+
+        // Tuples are generic structs named after their number of items, 
+        // declared once per file.
+        if token.token_type == TokenType::Tuple {
+            output_type = format!("Tuple{}[{}]", token.type_arguments.len(),
+                token.type_arguments.iter()
+                    .map(|item| <TokenSet as GoGen>::build_type_declaration(
+                        item, reusability, options))
+                    .collect::<Vec<String>>().join(", "));
+        }
+
+        // As modules are ignored, local types are named after their module
+        // and their own name.
+        if token.parameters.contains(&TokenParameter::LocalType) {
+            // Local types are qualified once resolved.
+            let reusable_data = reusability.resolve_declaration(
+                token.custom_token_type.as_ref().unwrap(), &[]).ok();
+
+            if let Some(reusable_data) = reusable_data {
+                output_type = <ItemDeclarationDescriptor as GoReusability>
+                    ::produce_reusable_statement_from_struct_or_enum_token(
+                        reusable_data);
             }
         }
 
+        // Generic types are followed by their type arguments.
+        if token.token_type == TokenType::Custom && 
+            !token.type_arguments.is_empty() {
+            output_type = format!("{}[{}]", output_type, 
+                token.type_arguments.iter()
+                    .map(|argument| <TokenSet as GoGen>::
+                        build_type_declaration(argument, reusability, options))
+                    .collect::<Vec<String>>().join(", "));
+        }
+
+       // Types already being pointers, such as `*big.Int`s, are nullable.
+       if (token.parameters.contains(&TokenParameter::Optional) ||
+           token.parameters.contains(&TokenParameter::Nullable)) &&
+           !output_type.starts_with('*') {
+            output_type = format!("*{}", output_type);
+       }
+
        if token.parameters.contains(&TokenParameter::Pointer) ||
            token.parameters.contains(&TokenParameter::Reference) {
             warn!("Go: pointer and ref does the same thing.");
@@ -234,77 +1067,642 @@ impl GoGen for TokenSet {
             output_type = format!("[]{}", output_type);
        }
 
-       return format!("{}", output_type) 
+       return output_type
+    }
+
+    fn build_doc_comment(token: &TokenSet, indent: &str) -> Vec<String> {
+        let mut output: Vec<String> = token.docs.iter()
+            .map(|line| format!("{}// {}", indent, line).trim_end().into())
+            .collect();
+
+        // Deprecation notices are a paragraph of their own.
+        if let Some(message) = &token.deprecation {
+            if !output.is_empty() { output.push(format!("{}//", indent)); }
+            output.push(format!("{}// Deprecated: {}", indent, message));
+        }
+        return output;
+    }
+
+    fn build_tagged_union(
+        token: &TokenSet,
+        module_name: &str,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Vec<String> {
+        let union_name = format!("{}{}", module_name, token.token_name);
+        let tag_name = options.tag_field.to_upper_camel_case();
+        let tag_field = format!("{} string `json:{}`", 
+                                tag_name, quote(&options.tag_field, "go"));
+        // The union holds the constant it's made of, as a pointer to its
+        // struct.
+        let mut output = vec![
+            format!("type {} struct {{", union_name),
+            "    // Only the field of the constant held is set.".into()];
+        output.extend(token.childs.iter()
+            .map(|constant| format!("    {} *{}{}", 
+                constant.token_name, union_name, constant.token_name)));
+        output.push("}".into());
+        let mut marshal_cases = vec![];
+        let mut unmarshal_cases = vec![];
+
+        // Every constant is a struct, which adds the tag field when
+        // serialized.
+        for constant in &token.childs {
+            let constant_name =
+                format!("{}{}", union_name, constant.token_name);
+
+            output.push("".into());
+            output.extend(<TokenSet as GoGen>::build_doc_comment(constant, ""));
+            output.push(format!("type {} struct {{", constant_name));
+            if let Some(payload) = &constant.payload {
+                output.push(format!("    {}", <TokenSet as GoGen>::
+                    build_type_declaration(payload, reusability, options)));
+            }
+            // Fields are exported for `encoding/json` to serialize them.
+            for field in &constant.childs {
+                output.push(format!("    {} {} `json:{}`", 
+                    field.token_name.to_upper_camel_case(), 
+                    <TokenSet as GoGen>::build_type_declaration(
+                        field, reusability, options),
+                    quote(field.serialized_name.as_ref().unwrap(), "go")));
+            }
+            output.extend([
+                "}".into(),
+                "".into(),
+                format!("func (value {}) MarshalJSON() ([]byte, error) {{", 
+                        constant_name),
+                format!("    type plain {}", constant_name),
+                "    return json.Marshal(struct {".into(),
+                format!("        {}", tag_field),
+                "        plain".into(),
+                format!("    }}{{{:?}, plain(value)}})", constant.token_name),
+                "}".into()]);
+
+            marshal_cases.extend([
+                format!("    case value.{} != nil:", constant.token_name),
+                format!("        return json.Marshal(value.{})", 
+                        constant.token_name)]);
+            unmarshal_cases.extend([
+                format!("    case {:?}:", constant.token_name),
+                format!("        value.{} = &{}{{}}", 
+                        constant.token_name, constant_name),
+                format!("        return json.Unmarshal(data, value.{})", 
+                        constant.token_name)]);
+        }
+
+        // The union is serialized as the constant it holds, null when it
+        // holds none as its zero value.
+        output.extend([
+            "".into(),
+            format!("func (value {}) MarshalJSON() ([]byte, error) {{", 
+                    union_name),
+            "    switch {".into()]);
+        output.extend(marshal_cases);
+        output.extend([
+            "    }".into(),
+            "    return []byte(\"null\"), nil".into(),
+            "}".into()]);
+
+        // Reading a union requires its tag to be known first.
+        output.extend([
+            "".into(),
+            format!("func (value *{}) UnmarshalJSON(data []byte) error {{", 
+                    union_name),
+            format!("    *value = {}{{}}", union_name),
+            "    if string(data) == \"null\" {".into(),
+            "        return nil".into(),
+            "    }".into(),
+            "    var tagged struct {".into(),
+            format!("        {}", tag_field),
+            "    }".into(),
+            "    if err := json.Unmarshal(data, &tagged); err != nil {".into(),
+            "        return err".into(),
+            "    }".into(),
+            format!("    switch tagged.{} {{", tag_name)]);
+        output.extend(unmarshal_cases);
+        output.extend([
+            "    }".into(),
+            format!("    return fmt.Errorf(\"unknown {} %q\", tagged.{})",
+                    union_name, tag_name),
+            "}".into()]);
+
+        return output;
+    }
+
+    fn build_constructor(
+        token: &TokenSet,
+        module_name: &str,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Vec<String> {
+        let struct_name = format!("{}{}", module_name, token.token_name);
+        let (type_parameters, type_arguments) = 
+            if token.type_parameters.is_empty() {
+            ("".into(), "".into())
+        } else {
+            (format!("[{} any]", token.type_parameters.join(", ")),
+             format!("[{}]", token.type_parameters.join(", ")))
+        };
+
+        let mut output = vec![
+            "".into(),
+            format!("// New{} returns the default value of {}.", 
+                    struct_name, struct_name),
+            format!("func New{}{}() {}{} {{", 
+                    struct_name, type_parameters, struct_name, type_arguments),
+            format!("    return {}{}{{", struct_name, type_arguments)];
+
+        // Embedded structs with default values are built by their own
+        // constructor.
+        for base in token.bases.iter().filter(|base| 
+            base.childs.iter().any(|field| field.value.is_some())) {
+            let base_name = <TokenSet as GoGen>::build_type_declaration(
+                base, reusability, options);
+
+            output.push(format!("        {}: New{}(),", base_name, base_name));
+        }
+
+        // Optional fields are pointers, which can't point to constants.
+        for field in token.childs.iter().filter(|field| field.value.is_some()) {
+            let value = if field.parameters
+                .contains(&TokenParameter::Optional) {
+                let mut value_token = field.clone();
+                value_token.parameters.retain(
+                    |parameter| *parameter != TokenParameter::Optional);
+                let value_type = <TokenSet as GoGen>::build_type_declaration(
+                    &value_token, reusability, options);
+
+                format!("func() *{} {{ value := {}({}); return &value }}()",
+                        value_type, value_type, field.value_code("go"))
+            } else { field.value_code("go") };
+
+            output.push(format!("        {}: {},", field.token_name, value));
+        }
+        output.extend(["    }".into(), "}".into()]);
+
+        return output;
+    }
+
+    fn build_validate_method(
+        token: &TokenSet,
+        module_name: &str,
+        imports: &mut Vec<String>) -> Vec<String> {
+        let struct_name = format!("{}{}", module_name, token.token_name);
+        let type_arguments = if token.type_parameters.is_empty() { 
+            "".into() 
+        } else { 
+            format!("[{}]", token.type_parameters.join(", ")) 
+        };
+        let mut output = vec![
+            "".into(),
+            "// Validate checks the constraints of every field, returning the \
+            ones that".into(),
+            "// aren't satisfied.".into(),
+            format!("func (value {}{}) Validate() error {{", 
+                    struct_name, type_arguments),
+            "    var errs []error".into()];
+        // Patterns are compiled once, in variables declared before the
+        // method.
+        let mut patterns = vec![];
+
+        imports.push("errors".into());
+
+        for field in token.childs.iter()
+            .filter(|field| !field.constraints.is_empty()) {
+            let is_text = field.token_type == TokenType::String && 
+                !field.parameters.contains(&TokenParameter::Vector);
+            // Optional and nullable values are pointers, only checked when
+            // not nil. Vectors of optional values are always present.
+            let pointer = if field.is_optional_field() && 
+                field.is_nullable_field() { 
+                format!("value.{}.Value", field.token_name) 
+            } else { 
+                format!("value.{}", field.token_name) 
+            };
+            let is_optional = field.is_optional_field() || 
+                field.is_nullable_field();
+            let (value, indent) = if is_optional {
+                output.push(format!("    if {} != nil {{", pointer));
+                (format!("*{}", pointer), "    ")
+            } else { (pointer, "") };
+            let mut field_patterns = 0;
+
+            for constraint in &field.constraints {
+                let condition = match constraint {
+                    Constraint::Min(min) => 
+                        format!("{} < {}", value, min.to_code("go")),
+                    Constraint::Max(max) => 
+                        format!("{} > {}", value, max.to_code("go")),
+                    // Strings are measured in characters, not in bytes.
+                    Constraint::Length(min, max) if is_text => {
+                        imports.push("unicode/utf8".into());
+                        format!("length := utf8.RuneCountInString({}); \
+                                length < {} || length > {}", value, min, max)
+                    }
+                    Constraint::Length(min, max) => 
+                        format!("length := len({}); length < {} || length > {}",
+                                value, min, max),
+                    Constraint::Pattern(pattern) => {
+                        let variable_name = format!("{}{}Pattern{}",
+                            struct_name, 
+                            field.token_name.to_upper_camel_case(),
+                            if field_patterns == 0 { "".into() } 
+                            else { field_patterns.to_string() });
+
+                        field_patterns += 1;
+                        imports.push("regexp".into());
+
+                        if patterns.is_empty() { patterns.push("".into()); }
+                        patterns.push(format!(
+                            "var {} = regexp.MustCompile({})", 
+                            variable_name, quote(pattern, "go")));
+                        format!("!{}.MatchString({})", variable_name, value)
+                    }
+                    Constraint::NonEmpty => format!("len({}) == 0", value)
+                };
+
+                output.extend([
+                    format!("    {}if {} {{", indent, condition),
+                    format!("    {}    errs = append(errs, ValidationError{{\
+                            Field: {:?}, Constraint: {}}})", 
+                            indent, field.token_name, 
+                            quote(&constraint.describe(), "go")),
+                    format!("    {}}}", indent)]);
+            }
+
+            if is_optional { output.push("    }".into()); }
+        }
+
+        output.extend(["    return errors.Join(errs...)".into(), "}".into()]);
+
+        return patterns.into_iter().chain(output).collect();
+    }
+
+    fn build_module(
+        token: &TokenSet,
+        parent_prefix: &str,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions,
+        imports: &mut Vec<String>) -> Result<Vec<String>, Diagnostic> {
+        // Declarations are prefixed with the names of the modules they are
+        // nested in.
+        let module_prefix = format!("{}{}", parent_prefix, token.token_name);
+        let mut content_lines = vec![];
+
+        // As modules are ignored, their documentation is kept as a
+        // standalone comment.
+        if !token.docs.is_empty() {
+            content_lines.extend(<TokenSet as GoGen>::build_doc_comment(
+                    token, ""));
+            content_lines.push("".into());
+        }
+
+        // Loops through the Module's childs.
+        for secondary_item /* Such as struct or enum. */ in &token.childs {
+            if secondary_item.token_type == TokenType::Module {
+                content_lines.extend(<TokenSet as GoGen>::build_module(
+                        secondary_item, &module_prefix, reusability, options,
+                        imports)?);
+                continue;
+            }
+            if !secondary_item.is_declaration() { return 
+                    Err(Diagnostic::error(
+                        DiagnosticKind::FieldOutsideDeclaration,
+                        Some(secondary_item.span.clone()))) }
+
+            let used_types = secondary_item.used_types();
+
+            imports.extend(used_types.iter()
+                .filter(|token_type| token_type.is_well_known())
+                .flat_map(|token_type| <TokenSet as GoGen>::
+                    map_well_known_type(token_type, options).imports));
+            if options.wide_integers != WideIntegers::String && used_types
+                .iter().any(|token_type| token_type.is_big_integer()) {
+                imports.push("math/big".into());
+            }
+
+            content_lines.extend(<TokenSet as GoGen>::build_doc_comment(
+                    secondary_item, ""));
+
+            // Tagged unions are structs holding the struct of one of their
+            // constants.
+            if secondary_item.is_tagged_union() {
+                content_lines.extend(<TokenSet as GoGen>::
+                    build_tagged_union(secondary_item, 
+                                       &module_prefix,
+                                       reusability, 
+                                       options));
+                imports.extend(["encoding/json".into(), "fmt".into()]);
+                continue;
+            }
+
+            if secondary_item.token_type == TokenType::Constant {
+                content_lines.push(format!("const {}{} {} = {}", 
+                    module_prefix,
+                    secondary_item.token_name,
+                    <TokenSet as GoGen>::build_type_declaration(
+                        &secondary_item.childs[0], reusability, options),
+                    secondary_item.value_code("go")));
+                continue;
+            }
+
+            // Extern types are aliases of the type they are mapped to, their
+            // imports being added to the file ones.
+            if secondary_item.token_type == TokenType::Extern {
+                content_lines.push(format!("type {}{} = {}", 
+                    module_prefix,
+                    secondary_item.token_name,
+                    secondary_item.extern_mapping("go")?
+                        .custom_token_type.as_ref().unwrap()));
+                imports.extend(secondary_item.extern_imports("go"));
+                continue;
+            }
+
+            // Newtypes are defined types, while aliases are other names
+            // for their type.
+            if secondary_item.token_type == TokenType::Alias ||
+                secondary_item.token_type == TokenType::NewType {
+                content_lines.push(format!("type {}{} {}{}", 
+                    module_prefix,
+                    secondary_item.token_name,
+                    if secondary_item.token_type == TokenType::Alias { 
+                        "= " } else { "" },
+                    <TokenSet as GoGen>::build_type_declaration(
+                        &secondary_item.childs[0], reusability, options)));
+                continue;
+            }
+
+            if secondary_item.token_type == TokenType::Structure {
+                // Type parameters of generic structs accept any type.
+                content_lines.push(format!("type {}{}{} {} {{", 
+                    module_prefix,
+                    secondary_item.token_name,
+                    if secondary_item.type_parameters.is_empty() { 
+                        "".into() 
+                    } else { 
+                        format!("[{} any]", 
+                                secondary_item.type_parameters.join(", "))
+                    },
+                    <TokenSet as GoGen>::
+                    generate_keyword_from_token_type(secondary_item)));
+
+                // Extended structs are embedded, their fields being promoted
+                // as well once serialized.
+                content_lines.extend(secondary_item.bases.iter()
+                    .map(|base| format!("    {}", <TokenSet as GoGen>::
+                        build_type_declaration(base, reusability, options))));
+            } else {
+                // Enums are types their constants are made of.
+                content_lines.push(format!("type {}{} {}", 
+                    module_prefix,
+                    secondary_item.token_name,
+                    if secondary_item.has_integer_values() { "int64" } 
+                    else { "string" }));
+            }
+            // Loops through the inner types of the Struct/Enum.
+            
+            // For enums, the secondary item is not used to group types
+            // but as a part of the produced item title.
+
+            for inner_item in secondary_item.childs.iter().enumerate() {
+                if secondary_item.token_type == TokenType::Structure {
+                    let field = inner_item.1;
+                    // Absent values are omitted, and told apart from null
+                    // ones by `Nullable` when both are allowed.
+                    let (field_type, omit) = if field.is_optional_field() &&
+                        field.is_nullable_field() {
+                        let mut value_token = field.clone();
+                        value_token.parameters.retain(|parameter| 
+                            *parameter != TokenParameter::Optional &&
+                            *parameter != TokenParameter::Nullable);
+
+                        (format!("Nullable[{}]", <TokenSet as GoGen>::
+                            build_type_declaration(
+                                &value_token, reusability, options)),
+                         ",omitzero")
+                    } else {
+                        (<TokenSet as GoGen>::build_type_declaration(
+                            field, reusability, options),
+                         if field.is_optional_field() { ",omitempty" } 
+                         else { "" })
+                    };
+                    let tag = format!("{}{}", 
+                        field.serialized_name.as_ref().unwrap(), omit);
+
+                    content_lines.extend(<TokenSet as GoGen>::
+                        build_doc_comment(field, "    "));
+                    content_lines.push(format!("    {} {} `json:{}`",
+                        field.token_name, field_type, quote(&tag, "go")));
+                } else {
+                    let variable_name = 
+                        format!("{}{}{}",
+                            module_prefix,
+                            secondary_item.token_name,
+                            <TokenSet as GoGen>::build_type_declaration(
+                                inner_item.1, reusability, options)
+                            );
+                    content_lines.extend(<TokenSet as GoGen>::
+                        build_doc_comment(inner_item.1, ""));
+                    // Constants without values are named after
+                    // themselves and their position.
+                    let value = match &inner_item.1.value {
+                        Some(value) => value.to_code("go"),
+                        None => format!("\"{}-{}\"", 
+                                        variable_name, inner_item.0)
+                    };
+
+                    content_lines.push(
+                        format!("const {} {}{} = {}",
+                        variable_name, 
+                        module_prefix,
+                        secondary_item.token_name,
+                        value));
+                }
+            }
+
+            if secondary_item.token_type == TokenType::Structure {
+                content_lines.push("}".into());
+            }
+
+            // Fields of embedded structs are promoted, and can thus be
+            // validated as the struct own fields.
+            let flattened_item = secondary_item.with_inherited_fields();
+
+            if flattened_item.has_default_values() {
+                content_lines.extend(<TokenSet as GoGen>::
+                    build_constructor(secondary_item, 
+                                      &module_prefix,
+                                      reusability,
+                                      options));
+            }
+
+            if flattened_item.has_constraints() {
+                content_lines.extend(<TokenSet as GoGen>::
+                    build_validate_method(&flattened_item, 
+                                          &module_prefix,
+                                          imports));
+            }
+        }
+
+        return Ok(content_lines);
     }
 
     fn produce_go_build_in_single_file(
             source: Vec<TokenSet>,
             reusability: ReusableDeclarations,
             output_path: String,
-            output_package_name: String) 
-            -> Result<(), String> {
+            output_package_name: String,
+            options: &GeneratorOptions) 
+            -> Result<(), Diagnostic> {
         // Content is generated line by line and put there before being joined
         // and saved.
         let mut content_lines: Vec<String> = vec![];
 
         warn!("Go: due to language limitations, modules grouping is ignored.");
-        warn!("Go: due to language limitations, enums are set to constants.");
+        warn!("Go: due to language limitations, enums are set to typed \
+               constants.");
 
         content_lines.push(format!("package {}\n", output_package_name));
 
-        for root_item in source {
-            // Loops through the Module's childs.
-            for secondary_item /* Such as struct or enum. */ in 
-                root_item.childs {
-                if secondary_item.token_type != TokenType::Structure && 
-                    secondary_item.token_type != TokenType::Enumeration{ return 
-                        Err("Structs have to be defined before types."
-                           .into()) }
+        // Packages required by the generated code.
+        let mut imports: Vec<String> = vec![];
+        let has_validators = source.iter()
+            .any(|module| module.declarations().iter()
+                 .any(|(_, declaration)| declaration.has_constraints()));
+        let has_nullables = source.iter()
+            .any(|module| module.declarations().iter()
+                 .any(|(_, declaration)| 
+                      declaration.token_type == TokenType::Structure &&
+                      declaration.childs.iter().any(|field| 
+                          field.is_optional_field() && 
+                          field.is_nullable_field())));
+        let mut tuple_lengths: Vec<usize> = vec![];
 
-                if secondary_item.token_type == TokenType::Structure {
-                    content_lines.push(format!("type {}{} {} {{", 
-                        root_item.token_name,
-                        secondary_item.token_name,
-                        <TokenSet as GoGen>::
-                        generate_keyword_from_token_type(&secondary_item)));
-                }
-                // Loops through the inner types of the Struct/Enum.
-                
-                // For enums, the secondary item is not used to group types
-                // but as a part of the produced item title.
-
-                for inner_item in secondary_item.childs.iter().enumerate() {
-                    if secondary_item.token_type == TokenType::Structure {
-                        content_lines.push(format!("    {} {}",
-                            inner_item.1.token_name,
-                            <TokenSet as GoGen>::build_type_declaration(
-                              inner_item.1, &reusability)));
-                    } else {
-                        let variable_name = 
-                            format!("{}{}{}",
-                                root_item.token_name,
-                                secondary_item.token_name,
-                                <TokenSet as GoGen>::build_type_declaration(
-                                    inner_item.1, &reusability)
-                                );
-                        content_lines.push(
-                            format!("const {} = \"{}-{}\"",
-                            variable_name, variable_name, inner_item.0));
-                    }
+        for module in source.iter() {
+            for length in module.tuple_lengths() {
+                if !tuple_lengths.contains(&length) { 
+                    tuple_lengths.push(length); 
                 }
-
-                if secondary_item.token_type == TokenType::Structure {
-                    content_lines.push("}".into());
-                }
-                 
             }
         }
+        tuple_lengths.sort();
+
+        for root_item in source {
+            content_lines.extend(<TokenSet as GoGen>::build_module(
+                    &root_item, "", &reusability, options, &mut imports)?);
+        }
+
+        // Values that can be absent or null are shared by every struct.
+        if has_nullables {
+            imports.push("encoding/json".into());
+            content_lines.extend([
+                "".into(),
+                "// Nullable is a value that can be absent, null or set once \
+                serialized.".into(),
+                "type Nullable[T any] struct {".into(),
+                "    // Present tells if the value was serialized, even as \
+                null.".into(),
+                "    Present bool".into(),
+                "    // Value is nil when null.".into(),
+                "    Value *T".into(),
+                "}".into(),
+                "".into(),
+                "// IsZero tells if the value is absent, for `omitzero` to \
+                leave it out.".into(),
+                "// `omitzero` requires Go 1.24 or later.".into(),
+                "func (value Nullable[T]) IsZero() bool {".into(),
+                "    return !value.Present".into(),
+                "}".into(),
+                "".into(),
+                "func (value Nullable[T]) MarshalJSON() ([]byte, error) {"
+                    .into(),
+                "    return json.Marshal(value.Value)".into(),
+                "}".into(),
+                "".into(),
+                "func (value *Nullable[T]) UnmarshalJSON(data []byte) error {"
+                    .into(),
+                "    value.Present = true".into(),
+                "    return json.Unmarshal(data, &value.Value)".into(),
+                "}".to_string()]);
+        }
+
+        // Tuples are serialized as arrays, as by the other generators.
+        for length in tuple_lengths {
+            let parameters = (0..length)
+                .map(|index| format!("T{}", index))
+                .collect::<Vec<String>>();
+            let items = (0..length)
+                .map(|index| format!("tuple.Item{}", index))
+                .collect::<Vec<String>>();
+            let item_pointers = items.iter()
+                .map(|item| format!("&{}", item))
+                .collect::<Vec<String>>();
+            let tuple_type = format!(
+                "Tuple{}[{}]", length, parameters.join(", "));
+
+            imports.push("encoding/json".into());
+            content_lines.extend([
+                "".into(),
+                format!("// Tuple{} is a list of {} values of fixed types.", 
+                        length, length),
+                format!("type Tuple{}[{} any] struct {{", 
+                        length, parameters.join(", "))]);
+            content_lines.extend(parameters.iter().enumerate()
+                .map(|(index, parameter)| 
+                     format!("    Item{} {}", index, parameter)));
+            content_lines.extend([
+                "}".into(),
+                "".into(),
+                format!("func (tuple {}) MarshalJSON() ([]byte, error) {{", 
+                        tuple_type),
+                format!("    return json.Marshal([]any{{{}}})", 
+                        items.join(", ")),
+                "}".into(),
+                "".into(),
+                format!("func (tuple *{}) UnmarshalJSON(data []byte) error {{", 
+                        tuple_type),
+                format!("    return json.Unmarshal(data, &[]any{{{}}})", 
+                        item_pointers.join(", ")),
+                "}".to_string()]);
+        }
+
+        // Validation errors are shared by every struct.
+        if has_validators {
+            content_lines.extend([
+                "".into(),
+                "// ValidationError is a field value that doesn't satisfy one \
+                of its constraints.".into(),
+                "type ValidationError struct {".into(),
+                "    // Name of the field.".into(),
+                "    Field string".into(),
+                "    // Constraint as written in TypeCode, such as `min(1)`."
+                    .into(),
+                "    Constraint string".into(),
+                "}".into(),
+                "".into(),
+                "func (err ValidationError) Error() string {".into(),
+                "    return err.Field + \" doesn't satisfy \" + err.Constraint"
+                    .into(),
+                "}".to_string()]);
+        }
+
+        // Imports are known once every declaration has been generated, they
+        // are put right after the package clause.
+        imports.sort();
+        imports.dedup();
+        if !imports.is_empty() {
+            let mut import_lines = vec!["import (".to_string()];
+            import_lines.extend(imports.iter()
+                .map(|import| format!("    {:?}", import)));
+            import_lines.push(")\n".into());
+            content_lines.splice(1..1, import_lines);
+        }
 
         let content_lines = content_lines.join("\n");
 
         debug!("Generated content:\n{}", content_lines);
 
         try_write_bytes_to_file(output_path, content_lines.as_bytes())
+            .map_err(|reason| Diagnostic::error(
+                DiagnosticKind::OutputWriteFailed(reason), None))
     }
 }
 
@@ -318,97 +1716,607 @@ impl TSGen for TokenSet {
             TokenType::String | TokenType::Char => { "string" }
             TokenType::IntU8 | TokenType::IntI8 | TokenType::IntU16
                 | TokenType::IntI16 | TokenType::IntU32 | TokenType::IntI32
-                | TokenType::IntU64 | TokenType::IntI64 => { "number" }
+                | TokenType::IntU64 | TokenType::IntI64 | TokenType::IntU128
+                | TokenType::IntI128 | TokenType::BigInt | TokenType::Float32
+                | TokenType::Float64 => { "number" }
             TokenType::Module => { "namespace" }
             TokenType::Structure => { "interface" }
             TokenType::Enumeration => { "enum" }
+            TokenType::Alias | TokenType::NewType 
+                | TokenType::Extern => { "type" }
+            TokenType::Constant => { "const" }
             TokenType::Bool => { "boolean" }
+            // Field groups are expanded before generation.
+            TokenType::FieldGroup => { "" }
+            TokenType::Map => { "Record" }
+            TokenType::Vector | TokenType::Array 
+                | TokenType::Tuple => { "Array" }
+            TokenType::Set => { "Set" }
+            TokenType::Bytes | TokenType::Uuid | TokenType::DateTime
+                | TokenType::Date | TokenType::Duration 
+                | TokenType::Decimal => {
+                return <TokenSet as TSGen>::map_well_known_type(
+                    &token.token_type, &GeneratorOptions::default()).target }
             TokenType::Custom => { return token.custom_token_type.clone()
                 .unwrap() }
         }.into()
     }
 
+    fn known_annotations() -> &'static [&'static str] {
+        &["rename", "rename_all", "deprecated"]
+    }
+
+    fn map_well_known_type(token_type: &TokenType, options: &GeneratorOptions)
+        -> TypeMapping {
+        let keyword = token_type.well_known_keyword().unwrap_or_default();
+
+        if let Some(mapping) = options.type_mappings.get(keyword) {
+            return mapping.clone();
+        }
+
+        // Types are the ones they're serialized as: bytes are base64 
+        // strings, dates ISO 8601 strings and durations nanoseconds.
+        match token_type {
+            TokenType::Duration => TypeMapping::new("number", &[]),
+            _ => TypeMapping::new("string", &[])
+        }
+    }
+
     fn build_type_declaration(
-        token: &TokenSet, reusability: &ReusableDeclarations) -> String {
-       let mut output_type = format!("{}", <TokenSet as TSGen>::
-                                     generate_keyword_from_token_type(token));
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> String {
+        let output_type =
+            <TokenSet as TSGen>::build_type(token, reusability, options);
+
+        if token.parameters.contains(&TokenParameter::Optional) { 
+            format!("{}?: {};", 
+                    <TokenSet as TSGen>::build_property_key(token), 
+                    output_type)
+        } else {
+            format!("{}: {};",
+                    <TokenSet as TSGen>::build_property_key(token), 
+                    output_type)
+        }
+    }
+
+    fn build_property_name(token: &TokenSet) -> String {
+        token.serialized_name.clone()
+            .unwrap_or(token.token_name.to_lower_camel_case())
+    }
+
+    fn build_property_key(token: &TokenSet) -> String {
+        let name = <TokenSet as TSGen>::build_property_name(token);
+        let is_identifier = name.chars().enumerate().all(|(index, character)|
+            character == '_' || character == '$' || 
+            character.is_ascii_alphabetic() || 
+            (index > 0 && character.is_ascii_digit()));
+
+        if is_identifier { name } else { quote(&name, "ts") }
+    }
+
+    fn build_type(
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> String {
+       let mut output_type = <TokenSet as TSGen>::
+           generate_keyword_from_token_type(token);
+
+        if token.token_type.is_well_known() {
+            output_type = <TokenSet as TSGen>::map_well_known_type(
+                &token.token_type, options).target;
+        }
+
+        if token.token_type.is_wide_integer() {
+            output_type = match options.wide_integers {
+                WideIntegers::Number => "number",
+                WideIntegers::BigInt => "bigint",
+                WideIntegers::String => "string"
+            }.into();
+        }
+
+        // Type arguments can't be left out, optional ones are nullable
+        // instead.
+        let build_argument = |argument: &TokenSet| {
+            let argument_type = <TokenSet as TSGen>::build_type(
+                argument, reusability, options);
+
+            if argument.parameters.contains(&TokenParameter::Optional) &&
+                !argument.parameters.contains(&TokenParameter::Nullable) {
+                format!("{} | null", argument_type)
+            } else { argument_type }
+        };
+
+        // Maps are made of their key and value types. As a `Record` with enum
+        // keys would require every constant to be present, it's made partial.
+        if token.token_type == TokenType::Map {
+            let key = &token.type_arguments[0];
+
+            output_type = format!("Record<{}, {}>", 
+                build_argument(key), build_argument(&token.type_arguments[1]));
+
+            if key.token_type == TokenType::Custom {
+                output_type = format!("Partial<{}>", output_type);
+            }
+        }
+
+        // Collections are made of their item type, arrays being tuples of
+        // their length.
+        if matches!(token.token_type, 
+                    TokenType::Vector | TokenType::Array | TokenType::Set) {
+            let item = build_argument(&token.type_arguments[0]);
+
+            output_type = match token.token_type {
+                TokenType::Vector if item.contains(" | ") => 
+                    format!("({})[]", item),
+                TokenType::Vector => format!("{}[]", item),
+                TokenType::Array => format!("[{}]", 
+                    vec![item; token.array_length.unwrap()].join(", ")),
+                _ => format!("Set<{}>", item)
+            };
+        }
+
+        if token.token_type == TokenType::Tuple {
+            output_type = format!("[{}]", token.type_arguments.iter()
+                .map(build_argument)
+                .collect::<Vec<String>>().join(", "));
+        }
 
         if token.parameters.contains(&TokenParameter::LocalType) {
-            let reusable_data = reusability.
-                    find_declaration_descriptor_with_declaration_name( 
-                        token.custom_token_type.clone().unwrap());
+            // Local types are qualified once resolved.
+            let reusable_data = reusability.resolve_declaration(
+                token.custom_token_type.as_ref().unwrap(), &[]).ok();
 
             debug!("Found a token with the `local` parameter.");
 
-            if reusable_data.is_some() {
+            if let Some(reusable_data) = reusable_data {
                 output_type = <ItemDeclarationDescriptor as TSReusability>
                     ::produce_reusable_statement_from_struct_or_enum_token(
-                        reusable_data.unwrap());
+                        reusable_data);
             } 
         }
 
+        // Generic types are followed by their type arguments.
+        if token.token_type == TokenType::Custom && 
+            !token.type_arguments.is_empty() {
+            output_type = format!("{}<{}>", output_type, 
+                token.type_arguments.iter().map(build_argument)
+                    .collect::<Vec<String>>().join(", "));
+        }
+
+        // Vectors of nullable values hold them, without being nullable.
+        if token.parameters.contains(&TokenParameter::Nullable) {
+            output_type = if token.parameters
+                .contains(&TokenParameter::Vector) {
+                format!("({} | null)", output_type)
+            } else { format!("{} | null", output_type) };
+        }
+
         if token.parameters.contains(&TokenParameter::Vector) {
             output_type = format!("{}[]", output_type);
         } 
 
-        if token.parameters.contains(&TokenParameter::Optional) { 
-            format!("{}?: {};", 
-                    token.token_name.to_lower_camel_case(), output_type)
-        } else {
-            format!("{}: {};",
-                    token.token_name.to_lower_camel_case(), output_type)
+        return output_type
+    }
+
+    fn build_doc_comment(token: &TokenSet, indent: &str) -> Vec<String> {
+        let mut lines = token.docs.clone();
+
+        if let Some(message) = &token.deprecation {
+            lines.push(format!("@deprecated {}", message));
+        }
+
+        match lines.as_slice() {
+            [] => vec![],
+            [line] => vec![format!("{}/** {} */", indent, line)],
+            lines => {
+                let mut output = vec![format!("{}/**", indent)];
+                output.extend(lines.iter().map(|line| 
+                    format!("{} * {}", indent, line).trim_end().into()));
+                output.push(format!("{} */", indent));
+                output
+            }
         }
     }
     
+    fn build_tagged_union(
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations, 
+        options: &GeneratorOptions) -> Vec<String> {
+        let mut output = vec![
+            format!("    export type {} =", token.token_name)];
+
+        for constant in &token.childs {
+            let tag = format!("{}: {:?}", 
+                              options.tag_field, constant.token_name);
+            let member = match &constant.payload {
+                Some(payload) => format!("{{ {} }} & {}", tag,
+                    <TokenSet as TSGen>::build_type(
+                        payload, reusability, options)),
+                None => format!("{{ {}; {}}}", tag, constant.childs.iter()
+                    .map(|field| format!("{} ", <TokenSet as TSGen>::
+                        build_type_declaration(field, reusability, options)))
+                    .collect::<String>())
+            };
+
+            output.extend(<TokenSet as TSGen>::build_doc_comment(
+                    constant, "        "));
+            output.push(format!("        | {}", member));
+        }
+
+        output.last_mut().unwrap().push(';');
+        return output;
+    }
+
+    fn build_value(
+        value_type: &TokenSet, 
+        value: &Literal, 
+        options: &GeneratorOptions) -> String {
+        if !value_type.token_type.is_wide_integer() { 
+            return value.to_code("ts"); 
+        }
+
+        match options.wide_integers {
+            WideIntegers::Number => value.to_code("ts"),
+            WideIntegers::BigInt => format!("{}n", value.to_code("ts")),
+            WideIntegers::String => format!("\"{}\"", value.to_code("ts"))
+        }
+    }
+
+    fn build_default_factory(token: &TokenSet, options: &GeneratorOptions)
+        -> Vec<String> {
+        let (type_parameters, struct_type) = 
+            if token.type_parameters.is_empty() {
+            ("".into(), token.token_name.clone())
+        } else {
+            let type_parameters = format!("<{}>", 
+                                          token.type_parameters.join(", "));
+            (type_parameters.clone(), 
+             format!("{}{}", token.token_name, type_parameters))
+        };
+        let defaulted_fields = token.childs.iter()
+            .filter(|field| field.value.is_some());
+
+        return vec![
+            format!("    /** Builds a `{}` out of `fields`, defaulted fields \
+                    being optional. */", token.token_name),
+            format!("    export function default{}{}(", 
+                    token.token_name, type_parameters),
+            format!("        fields: Omit<{}, {}> & Partial<{}>): {} {{",
+                    struct_type,
+                    defaulted_fields.clone()
+                        .map(|field| quote(
+                             &<TokenSet as TSGen>::build_property_name(field), 
+                             "ts"))
+                        .collect::<Vec<String>>().join(" | "),
+                    struct_type, struct_type),
+            format!("        return {{ {}...fields }};", defaulted_fields
+                .map(|field| format!("{}: {}, ",
+                     <TokenSet as TSGen>::build_property_key(field), 
+                     <TokenSet as TSGen>::build_value(
+                         field, field.value.as_ref().unwrap(), options)))
+                .collect::<String>()),
+            "    }".into()];
+    }
+
+    fn build_validator(token: &TokenSet, options: &GeneratorOptions)
+        -> Vec<String> {
+        let (type_parameters, struct_type) = 
+            if token.type_parameters.is_empty() {
+            ("".into(), token.token_name.clone())
+        } else {
+            let type_parameters = format!("<{}>", 
+                                          token.type_parameters.join(", "));
+            (type_parameters.clone(), 
+             format!("{}{}", token.token_name, type_parameters))
+        };
+        let mut output = vec![
+            format!("    /** Lists the constraints the fields of `{}` don't \
+                    satisfy. */", token.token_name),
+            format!("    export function validate{}{}(value: {}): \
+                    ValidationError[] {{", 
+                    token.token_name, type_parameters, struct_type),
+            "        const errors: ValidationError[] = [];".into()];
+        // Patterns are compiled once, in constants declared before the
+        // function.
+        let mut patterns = vec![];
+
+        for field in token.childs.iter()
+            .filter(|field| !field.constraints.is_empty()) {
+            // Quoted keys are accessed with brackets.
+            let key = <TokenSet as TSGen>::build_property_key(field);
+            let value = if key.starts_with('"') { format!("value[{}]", key) } 
+                else { format!("value.{}", key) };
+            let is_optional = field.parameters
+                .contains(&TokenParameter::Optional) || 
+                field.is_nullable_field();
+            // Strings are measured in characters, not in UTF-16 units.
+            let length = if field.parameters
+                .contains(&TokenParameter::Vector) || 
+                field.token_type == TokenType::Vector { 
+                format!("{}.length", value)
+            } else if field.token_type == TokenType::Set { 
+                format!("{}.size", value)
+            } else if field.token_type == TokenType::Map { 
+                format!("Object.keys({}).length", value)
+            } else { 
+                format!("[...{}].length", value) 
+            };
+            let indent = if is_optional { "    " } else { "" };
+            // Wide integers that aren't numbers are compared as `bigint`s.
+            let (compared, bound) = match options.wide_integers {
+                _ if !field.token_type.is_wide_integer() => 
+                    (value.clone(), ""),
+                WideIntegers::Number => (value.clone(), ""),
+                WideIntegers::BigInt => (value.clone(), "n"),
+                WideIntegers::String => (format!("BigInt({})", value), "n")
+            };
+
+            // Loose equality to `null` matches `undefined` as well.
+            if field.is_nullable_field() {
+                output.push(format!("        if ({} != null) {{", value));
+            } else if is_optional {
+                output.push(format!("        if ({} !== undefined) {{", value));
+            }
+            let mut field_patterns = 0;
+
+            for constraint in &field.constraints {
+                let condition = match constraint {
+                    Constraint::Min(min) => format!("{} < {}{}", 
+                        compared, min.to_code("ts"), bound),
+                    Constraint::Max(max) => format!("{} > {}{}", 
+                        compared, max.to_code("ts"), bound),
+                    Constraint::Length(min, max) => 
+                        format!("{} < {} || {} > {}", length, min, length, max),
+                    Constraint::Pattern(pattern) => {
+                        let constant_name = format!("{}{}Pattern{}",
+                            token.token_name.to_lower_camel_case(), 
+                            field.token_name.to_upper_camel_case(),
+                            if field_patterns == 0 { "".into() } 
+                            else { field_patterns.to_string() });
+
+                        field_patterns += 1;
+
+                        patterns.push(format!("    const {} = new RegExp({});", 
+                                              constant_name, 
+                                              quote(pattern, "ts")));
+                        format!("!{}.test({})", constant_name, value)
+                    }
+                    Constraint::NonEmpty => format!("{} === 0", length)
+                };
+
+                output.extend([
+                    format!("        {}if ({}) {{", indent, condition),
+                    format!("        {}    errors.push({{ field: {}, \
+                            constraint: {} }});", indent, 
+                            quote(&<TokenSet as TSGen>::
+                                  build_property_name(field), "ts"), 
+                            quote(&constraint.describe(), "ts")),
+                    format!("        {}}}", indent)]);
+            }
+
+            if is_optional { output.push("        }".into()); }
+        }
+
+        output.extend(["        return errors;".into(), "    }".into()]);
+
+        return patterns.into_iter().chain(output).collect();
+    }
+
+    fn build_module(
+        token: &TokenSet,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Result<Vec<String>, Diagnostic> {
+        let mut content_lines = <TokenSet as TSGen>::build_doc_comment(
+            token, "");
+        content_lines.push(format!("export namespace {} {{", 
+                                   token.token_name));
+
+        // Loops through the Module's childs.
+        for secondary_item in &token.childs {
+            // Nested modules are indented within their parent.
+            if secondary_item.token_type == TokenType::Module {
+                content_lines.extend(<TokenSet as TSGen>::build_module(
+                        secondary_item, reusability, options)?
+                    .into_iter().map(indent_line));
+                continue;
+            }
+            if !secondary_item.is_declaration() { return
+                    Err(Diagnostic::error(
+                        DiagnosticKind::FieldOutsideDeclaration,
+                        Some(secondary_item.span.clone()))) }
+
+            content_lines.extend(<TokenSet as TSGen>::build_doc_comment(
+                    secondary_item, "    "));
+
+            // Tagged unions are unions of object types, told apart by
+            // their tag field.
+            if secondary_item.is_tagged_union() {
+                content_lines.extend(<TokenSet as TSGen>::
+                    build_tagged_union(
+                        secondary_item, reusability, options));
+                continue;
+            }
+
+            if secondary_item.token_type == TokenType::Constant {
+                content_lines.push(format!("    export const {}: {} = {};",
+                    secondary_item.token_name,
+                    <TokenSet as TSGen>::build_type(
+                        &secondary_item.childs[0], reusability, options),
+                    <TokenSet as TSGen>::build_value(
+                        &secondary_item.childs[0], 
+                        secondary_item.value.as_ref().unwrap(), options)));
+                continue;
+            }
+
+            // Extern types are aliases of the type they are mapped to.
+            if secondary_item.token_type == TokenType::Extern {
+                content_lines.push(format!("    export type {} = {};", 
+                    secondary_item.token_name,
+                    secondary_item.extern_mapping("ts")?
+                        .custom_token_type.as_ref().unwrap()));
+                continue;
+            }
+
+            // Newtypes are branded with their name, so that only values
+            // explicitly cast to them are accepted.
+            if secondary_item.token_type == TokenType::Alias ||
+                secondary_item.token_type == TokenType::NewType {
+                let target = <TokenSet as TSGen>::build_type(
+                    &secondary_item.childs[0], reusability, options);
+
+                content_lines.push(
+                    if secondary_item.token_type == TokenType::Alias {
+                        format!("    export type {} = {};", 
+                                secondary_item.token_name, target)
+                    } else {
+                        format!("    export type {} = {} & \
+                                {{ readonly __brand: {:?} }};", 
+                                secondary_item.token_name, target,
+                                secondary_item.token_name)
+                    });
+                continue;
+            }
+
+            content_lines.push(format!("    export {} {}{}{} {{",
+                <TokenSet as TSGen>::
+                generate_keyword_from_token_type(secondary_item),
+                secondary_item.token_name,
+                if secondary_item.type_parameters.is_empty() { "".into() }
+                else { format!("<{}>", 
+                               secondary_item.type_parameters.join(", ")) },
+                if secondary_item.bases.is_empty() { "".into() }
+                else { format!(" extends {}", secondary_item.bases.iter()
+                    .map(|base| <TokenSet as TSGen>::build_type(
+                        base, reusability, options))
+                    .collect::<Vec<String>>().join(", ")) }
+                ));
+
+            // Loops through the inner types of the Struct/Enum
+            for inner_item in &secondary_item.childs {
+                // If the inner item is a child of an enum, only the custom
+                // type is used.
+                content_lines.extend(<TokenSet as TSGen>::
+                    build_doc_comment(inner_item, "        "));
+                if secondary_item.token_type == TokenType::Structure {
+                    content_lines.push(format!("        {}",
+                        <TokenSet as TSGen>::build_type_declaration(
+                            inner_item, reusability, options)));
+                } else {
+                    let value = inner_item.value.as_ref()
+                        .map(|value| format!(" = {}", value.to_code("ts")))
+                        .unwrap_or_default();
+
+                    content_lines.push(format!("        {}{},",
+                        inner_item.custom_token_type.as_ref().unwrap(), 
+                        value));
+                }
+            }
+            content_lines.push("    }".into());
+
+            // Factories and validators handle the fields of the extended
+            // interfaces as well.
+            let flattened_item = secondary_item.with_inherited_fields();
+
+            if flattened_item.has_default_values() {
+                content_lines.extend(<TokenSet as TSGen>::
+                    build_default_factory(&flattened_item, options));
+            }
+
+            if flattened_item.has_constraints() {
+                content_lines.extend(<TokenSet as TSGen>::
+                    build_validator(&flattened_item, options));
+            }
+        }
+        content_lines.push("}".into());
+
+        return Ok(content_lines);
+    }
+
     fn produce_ts_build_in_single_file(
             source: Vec<TokenSet>, 
             reusability: ReusableDeclarations,
-            output_path: String)
-            -> Result<(), String> {
+            output_path: String,
+            options: &GeneratorOptions)
+            -> Result<(), Diagnostic> {
        // Content is generated line by line and is put here before being joined
        // at save time.
        let mut content_lines: Vec<String> = vec![];
+       let has_validators = source.iter()
+           .any(|module| module.declarations().iter()
+                .any(|(_, declaration)| declaration.has_constraints()));
 
        warn!("Typescript: Integers and floats precision is lost.");
        warn!("Typescript: Pointers and references are not used.");
 
+       if options.wide_integers == WideIntegers::Number && source.iter()
+           .flat_map(|module| module.declarations())
+           .any(|(_, declaration)| declaration.used_types().iter()
+                .any(|token_type| token_type.is_wide_integer())) {
+           warn!("Typescript: Integers wider than 53 bits are numbers, use \
+                 the `bigint` or `string` wide integers strategy to keep \
+                 their precision.");
+       }
+
+       // Extern and well-known types import the first name of the type they
+       // are mapped to from their imports, at the top of the file.
+       let mut mappings = vec![];
+
+       for (_, declaration) in source.iter()
+           .flat_map(|module| module.declarations()) {
+           if let Ok(mapping) = declaration.extern_mapping("ts") {
+               mappings.push(TypeMapping { 
+                   target: mapping.custom_token_type.clone().unwrap(), 
+                   imports: declaration.extern_imports("ts") });
+           }
+           mappings.extend(declaration.used_types().iter()
+               .filter(|token_type| token_type.is_well_known())
+               .map(|token_type| <TokenSet as TSGen>::map_well_known_type(
+                   token_type, options)));
+       }
+
+       let mut imports = mappings.iter()
+           .flat_map(|mapping| {
+               let imported_name = mapping.target
+                   .split(|c: char| !c.is_alphanumeric() && c != '_')
+                   .next().unwrap_or_default();
+
+               mapping.imports.iter().map(move |import| format!(
+                   "import type {{ {} }} from {:?};", imported_name, import))
+           })
+           .collect::<Vec<String>>();
+       imports.sort();
+       imports.dedup();
+       if !imports.is_empty() {
+           content_lines.extend(imports);
+           content_lines.push("".into());
+       }
+
        for root_item in source {
             // When generating TypeScript code, a root_item always has to be a
             // module.
             if root_item.token_type != TokenType::Module { return 
-                Err("Modules have to be declared before anything.".into()) }
-
-            content_lines.push(format!("export namespace {} {{", 
-                                       root_item.token_name));
-
-            // Loops through the Module's childs.
-            for secondary_item in root_item.childs {
-                if secondary_item.token_type != TokenType::Structure &&
-                    secondary_item.token_type != TokenType::Enumeration { return
-                        Err("Structs/Enums have to be defined after modules."
-                            .into())}
-
-                content_lines.push(format!("    export {} {} {{",
-                    <TokenSet as TSGen>::
-                    generate_keyword_from_token_type(&secondary_item),
-                    secondary_item.token_name));
-
-                // Loops through the inner types of the Struct/Enum
-                for inner_item in secondary_item.childs {
-                    // If the inner item is a child of an enum, only the custom
-                    // type is used.
-                    if secondary_item.token_type == TokenType::Structure {
-                        content_lines.push(format!("        {}",
-                            <TokenSet as TSGen>::build_type_declaration(
-                                &inner_item, &reusability)));
-                    } else {
-                        content_lines.push(format!("        {},",
-                            inner_item.custom_token_type.unwrap()));
-                    }
-                }
-                content_lines.push("    }".into());
-            }
-            content_lines.push("}".into());
+                Err(Diagnostic::error(
+                    DiagnosticKind::DeclarationOutsideModule(
+                        root_item.token_type), 
+                    Some(root_item.span))) }
+
+            content_lines.extend(<TokenSet as TSGen>::build_module(
+                    &root_item, &reusability, options)?);
+        }
+
+        // Validation errors are shared by every namespace.
+        if has_validators {
+            content_lines.extend([
+                "/** A field value that doesn't satisfy one of its \
+                constraints. */".into(),
+                "export interface ValidationError {".into(),
+                "    /** Name of the field. */".into(),
+                "    field: string;".into(),
+                "    /** Constraint as written in TypeCode, such as \
+                `min(1)`. */".into(),
+                "    constraint: string;".into(),
+                "}".to_string()]);
         }
 
        let content_lines = content_lines.join("\n");
@@ -416,5 +2324,433 @@ impl TSGen for TokenSet {
        debug!("Generated content:\n{}", content_lines);
 
        try_write_bytes_to_file(output_path, content_lines.as_bytes())
+           .map_err(|reason| Diagnostic::error(
+               DiagnosticKind::OutputWriteFailed(reason), None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::diagnostics::Diagnostics;
+    use crate::engine::lexer::tokenize;
+    use crate::engine::parser::Parser;
+    use crate::engine::reusability::resolve_deprecations;
+
+    // Code generated in `language` from `source`, which must be free of
+    // errors.
+    fn generate(source: &str, language: &str, options: &GeneratorOptions)
+        -> String {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(source, "test.tc", &mut diagnostics);
+        let modules = Parser::new(&lexemes, &mut diagnostics).parse_file();
+        let mut token_sets = TokenSet::token_sets_from_ast(&modules);
+        let reusability = ReusableDeclarations::from_token_sets_vec(
+            token_sets.clone());
+
+        resolve_deprecations(&mut token_sets, &mut diagnostics);
+        reusability.resolve_local_types(&mut token_sets, &mut diagnostics);
+        assert!(!diagnostics.has_errors(), "{:?}", diagnostics.entries);
+
+        // Generators write to a file, named after the test for tests to run
+        // in parallel.
+        let path = std::env::temp_dir().join(format!("typecode-{}-{}.{}", 
+            std::process::id(), 
+            std::thread::current().name().unwrap().replace("::", "-"), 
+            language));
+        let output_path = path.display().to_string();
+
+        match language {
+            #[cfg(feature = "rust-gen")]
+            "rs" => <TokenSet as RustGen>::produce_rs_build_in_single_file(
+                token_sets, reusability, output_path, options),
+            #[cfg(feature = "go-gen")]
+            "go" => <TokenSet as GoGen>::produce_go_build_in_single_file(
+                token_sets, reusability, output_path, "test".into(), options),
+            #[cfg(feature = "ts-gen")]
+            "ts" => <TokenSet as TSGen>::produce_ts_build_in_single_file(
+                token_sets, reusability, output_path, options),
+            _ => panic!("unknown language {}", language)
+        }.unwrap();
+
+        let output = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        return output;
+    }
+
+    // Asserts that `output` contains every line of `expected`, in a row.
+    fn assert_lines(output: &str, expected: &str) {
+        assert!(output.contains(expected), 
+                "expected:\n{}\nin:\n{}", expected, output);
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_tagged_union_fields_are_exported() {
+        let output = generate(
+            "module Shop\nenum Shape\n    Circle { float_64 radius }\n    \
+             Square { @rename(\"side-length\") float_64 side_length }\n", 
+            "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "type ShopShapeCircle struct {\n    \
+             Radius float64 `json:\"radius\"`\n}");
+        assert_lines(&output, 
+            "type ShopShapeSquare struct {\n    \
+             SideLength float64 `json:\"side-length\"`\n}");
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_tagged_unions_are_decoded_where_used() {
+        let output = generate(
+            "module Shop\nstruct CircleData\n    float_64 Radius\n\
+             enum Shape\n    Circle(CircleData)\n    Empty\n\
+             struct Home\n    Shape local Shape\n    Shape local vec Shapes\n",
+            "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "type ShopShape struct {\n    \
+             // Only the field of the constant held is set.\n    \
+             Circle *ShopShapeCircle\n    Empty *ShopShapeEmpty\n}");
+        assert_lines(&output, 
+            "type ShopHome struct {\n    Shape ShopShape `json:\"shape\"`\n    \
+             Shapes []ShopShape `json:\"shapes\"`\n}");
+        assert_lines(&output, 
+            "func (value ShopShape) MarshalJSON() ([]byte, error) {\n    \
+             switch {\n    case value.Circle != nil:\n        \
+             return json.Marshal(value.Circle)\n");
+        assert_lines(&output, 
+            "func (value *ShopShape) UnmarshalJSON(data []byte) error {\n");
+        assert_lines(&output, 
+            "    switch tagged.Kind {\n    case \"Circle\":\n        \
+             value.Circle = &ShopShapeCircle{}\n        \
+             return json.Unmarshal(data, value.Circle)\n");
+    }
+
+    const ENUM_VALUES: &str = "module M\nenum Level\n    Low = 1\n    \
+        High = -10\n    Max\nenum Mode\n    Fast = \"fast\"\n    \
+        Safe = \"sa\\\"fe\"\n";
+
+    #[test]
+    #[cfg(feature = "rust-gen")]
+    fn rust_enum_values() {
+        let options = GeneratorOptions { serde: true, ..Default::default() };
+        let output = generate(ENUM_VALUES, "rs", &options);
+
+        assert_lines(&output, 
+            "    #[repr(i64)]\n    pub enum Level {\n        \
+             #[default]\n        Low = 1,\n        High = -10,\n        \
+             Max = -9,\n    }");
+        assert_lines(&output, 
+            "        #[serde(rename = \"fast\")]\n        Fast,\n        \
+             #[serde(rename = \"sa\\\"fe\")]\n        Safe,\n");
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_enum_values() {
+        let output = generate(ENUM_VALUES, "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "type MLevel int64\nconst MLevelLow MLevel = 1\n\
+             const MLevelHigh MLevel = -10\nconst MLevelMax MLevel = -9\n");
+        assert_lines(&output, 
+            "type MMode string\nconst MModeFast MMode = \"fast\"\n\
+             const MModeSafe MMode = \"sa\\\"fe\"");
+    }
+
+    #[test]
+    #[cfg(feature = "ts-gen")]
+    fn ts_enum_values() {
+        let output = generate(ENUM_VALUES, "ts", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "    export enum Level {\n        Low = 1,\n        \
+             High = -10,\n        Max = -9,\n    }");
+        assert_lines(&output, 
+            "    export enum Mode {\n        Fast = \"fast\",\n        \
+             Safe = \"sa\\\"fe\",\n    }");
+    }
+
+    const VALIDATORS: &str = "module M\nstruct User\n    \
+        int_u8 Age [min(18), max(130)]\n    \
+        string Code [pattern(\"^[a-z]+$\"), pattern(\"x\")]\n    \
+        string optional Nickname [length(3, 20)]\n    \
+        string vec Tags [non_empty]\n";
+
+    #[test]
+    #[cfg(feature = "rust-gen")]
+    fn rust_validators() {
+        let output = generate(VALIDATORS, "rs", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "    static USER_CODE_PATTERN_1: std::sync::LazyLock<regex::Regex> \
+             =\n        std::sync::LazyLock::new(|| \
+             regex::Regex::new(\"x\").unwrap());\n    impl User {");
+        assert_lines(&output, 
+            "            if self.age < 18 {\n                \
+             errors.push(super::ValidationError {\n                    \
+             field: \"age\", constraint: \"min(18)\" });\n");
+        assert_lines(&output, 
+            "            if !USER_CODE_PATTERN.is_match(&self.code) {\n");
+        assert_lines(&output, 
+            "            if let Some(value) = &self.nickname {\n");
+        assert_lines(&output, 
+            "if !(3..=20).contains(&value.chars().count()) {\n");
+        assert_lines(&output, "            if self.tags.is_empty() {\n");
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_validators() {
+        let output = generate(VALIDATORS, "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "\nvar MUserCodePattern = regexp.MustCompile(\"^[a-z]+$\")\n\
+             var MUserCodePattern1 = regexp.MustCompile(\"x\")\n\n\
+             // Validate checks");
+        assert_lines(&output, 
+            "    if value.Age < 18 {\n        errs = append(errs, \
+             ValidationError{Field: \"Age\", Constraint: \"min(18)\"})\n");
+        assert_lines(&output, 
+            "    if !MUserCodePattern.MatchString(value.Code) {\n");
+        assert_lines(&output, 
+            "    if value.Nickname != nil {\n        if length := \
+             utf8.RuneCountInString(*value.Nickname); \
+             length < 3 || length > 20 {\n");
+        assert_lines(&output, "    if len(value.Tags) == 0 {\n");
+    }
+
+    #[test]
+    #[cfg(feature = "ts-gen")]
+    fn ts_validators() {
+        let output = generate(VALIDATORS, "ts", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "    const userCodePattern = new RegExp(\"^[a-z]+$\");\n    \
+             const userCodePattern1 = new RegExp(\"x\");\n    \
+             /** Lists the constraints");
+        assert_lines(&output, 
+            "        if (value.age < 18) {\n            \
+             errors.push({ field: \"age\", constraint: \"min(18)\" });\n");
+        assert_lines(&output, 
+            "        if (!userCodePattern.test(value.code)) {\n");
+        assert_lines(&output, 
+            "        if (value.nickname !== undefined) {\n            \
+             if ([...value.nickname].length < 3 || \
+             [...value.nickname].length > 20) {\n");
+        assert_lines(&output, "        if (value.tags.length === 0) {\n");
+    }
+
+    const RENAMES: &str = "module M\nstruct Order\n    string OrderId\n    \
+        @rename(\"total-price\")\n    int_u32 optional TotalPrice\n\
+        @rename_all(\"snake_case\")\nstruct Line\n    string ItemName\n";
+
+    #[test]
+    #[cfg(feature = "rust-gen")]
+    fn rust_renames() {
+        let options = GeneratorOptions { serde: true, ..Default::default() };
+        let output = generate(RENAMES, "rs", &options);
+
+        assert_lines(&output, 
+            "        #[serde(rename = \"orderId\")]\n        \
+             pub order_id: String,\n        \
+             #[serde(rename = \"total-price\")]\n");
+        assert_lines(&output, 
+            "    pub struct Line {\n        pub item_name: String,\n");
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_renames() {
+        let output = generate(RENAMES, "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "    OrderId string `json:\"orderId\"`\n    \
+             TotalPrice *uint32 `json:\"total-price,omitempty\"`\n");
+        assert_lines(&output, "    ItemName string `json:\"item_name\"`\n");
+    }
+
+    #[test]
+    #[cfg(feature = "ts-gen")]
+    fn ts_renames() {
+        let output = generate(RENAMES, "ts", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "        orderId: string;\n        \"total-price\"?: number;\n");
+        assert_lines(&output, "        item_name: string;\n");
+    }
+
+    const TAGGED_UNIONS: &str = "module M\nstruct CircleData\n    \
+        float_64 Radius\nenum Shape\n    Circle(CircleData)\n    \
+        Square { @rename(\"side-length\") float_64 Side }\n    Empty\n";
+
+    #[test]
+    #[cfg(feature = "rust-gen")]
+    fn rust_tagged_unions() {
+        let options = GeneratorOptions { 
+            serde: true, tag_field: "type".into(), ..Default::default() };
+        let output = generate(TAGGED_UNIONS, "rs", &options);
+
+        assert_lines(&output, 
+            "    #[serde(tag = \"type\")]\n    pub enum Shape {\n        \
+             Circle(super::M::CircleData),\n        \
+             Square { #[serde(rename = \"side-length\")] side: f64 },\n        \
+             Empty,\n    }");
+    }
+
+    #[test]
+    #[cfg(feature = "ts-gen")]
+    fn ts_tagged_unions() {
+        let options = GeneratorOptions { 
+            tag_field: "type".into(), ..Default::default() };
+        let output = generate(TAGGED_UNIONS, "ts", &options);
+
+        assert_lines(&output, 
+            "    export type Shape =\n        \
+             | { type: \"Circle\" } & M.CircleData\n        \
+             | { type: \"Square\"; \"side-length\": number; }\n        \
+             | { type: \"Empty\"; };");
+    }
+
+    const DEFAULTS: &str = "module M\nstruct Settings\n    \
+        int_u32 Retries = 3\n    string Name = \"a\\tb\"\n    \
+        bool optional Debug = true\n    float_64 Ratio = 0.5\n    \
+        string Other\n";
+
+    #[test]
+    #[cfg(feature = "rust-gen")]
+    fn rust_defaults() {
+        let options = GeneratorOptions { serde: true, ..Default::default() };
+        let output = generate(DEFAULTS, "rs", &options);
+
+        assert_lines(&output, 
+            "        #[serde(default = \"default_settings_retries\")]\n        \
+             pub retries: u32,\n");
+        assert_lines(&output, 
+            "    fn default_settings_name() -> String {\n        \
+             \"a\\tb\".into()\n    }\n    \
+             fn default_settings_debug() -> Option<bool> {\n        \
+             Some(true)\n    }\n");
+        assert_lines(&output, 
+            "            Self {\n                \
+             retries: default_settings_retries(),\n                \
+             name: default_settings_name(),\n                \
+             debug: default_settings_debug(),\n                \
+             ratio: default_settings_ratio(),\n                \
+             other: Default::default(),\n            }");
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_defaults() {
+        let output = generate(DEFAULTS, "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "func NewMSettings() MSettings {\n    return MSettings{\n        \
+             Retries: 3,\n        Name: \"a\\tb\",\n");
+        assert_lines(&output, 
+            "        Debug: func() *bool { value := bool(true); \
+             return &value }(),\n        Ratio: 0.5,\n    }\n}");
+    }
+
+    #[test]
+    #[cfg(feature = "ts-gen")]
+    fn ts_defaults() {
+        let output = generate(DEFAULTS, "ts", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "    export function defaultSettings(\n        \
+             fields: Omit<Settings, \"retries\" | \"name\" | \"debug\" | \
+             \"ratio\"> & Partial<Settings>): Settings {\n        \
+             return { retries: 3, name: \"a\\tb\", debug: true, ratio: 0.5, \
+             ...fields };\n    }");
+    }
+
+    const NULLABLES: &str = "module M\nstruct Profile\n    \
+        string nullable Bio\n    string optional Nick\n    \
+        string optional nullable Avatar\n";
+
+    #[test]
+    #[cfg(feature = "rust-gen")]
+    fn rust_nullables() {
+        let options = GeneratorOptions { serde: true, ..Default::default() };
+        let output = generate(NULLABLES, "rs", &options);
+
+        assert_lines(&output, 
+            "        pub bio: Option<String>,\n        \
+             #[serde(skip_serializing_if = \"Option::is_none\")]\n        \
+             pub nick: Option<String>,\n        \
+             #[serde(default, skip_serializing_if = \"Option::is_none\", \
+             with = \"::serde_with::rust::double_option\")]\n        \
+             pub avatar: Option<Option<String>>,\n");
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_nullables() {
+        let output = generate(NULLABLES, "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "    Bio *string `json:\"bio\"`\n    \
+             Nick *string `json:\"nick,omitempty\"`\n    \
+             Avatar Nullable[string] `json:\"avatar,omitzero\"`\n");
+        assert_lines(&output, 
+            "func (value *Nullable[T]) UnmarshalJSON(data []byte) error {\n    \
+             value.Present = true\n");
+    }
+
+    #[test]
+    #[cfg(feature = "ts-gen")]
+    fn ts_nullables() {
+        let output = generate(NULLABLES, "ts", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "        bio: string | null;\n        nick?: string;\n        \
+             avatar?: string | null;\n");
+    }
+
+    const TUPLES: &str = "module M\nstruct Point\n    \
+        (string, int_u8) Pair\n    (bool, int_u8, string) optional Triple\n";
+
+    #[test]
+    #[cfg(feature = "rust-gen")]
+    fn rust_tuples() {
+        let output = generate(TUPLES, "rs", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "        pub pair: (String, u8),\n        \
+             pub triple: Option<(bool, u8, String)>,\n");
+    }
+
+    #[test]
+    #[cfg(feature = "go-gen")]
+    fn go_tuples() {
+        let output = generate(TUPLES, "go", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "    Pair Tuple2[string, uint8] `json:\"pair\"`\n");
+        assert_lines(&output, 
+            "    Triple *Tuple3[bool, uint8, string] \
+             `json:\"triple,omitempty\"`\n");
+        assert_lines(&output, 
+            "type Tuple2[T0, T1 any] struct {\n    Item0 T0\n    Item1 T1\n}");
+        assert_lines(&output, 
+            "    return json.Marshal([]any{tuple.Item0, tuple.Item1, \
+             tuple.Item2})\n}");
+        assert_lines(&output, 
+            "    return json.Unmarshal(data, &[]any{&tuple.Item0, \
+             &tuple.Item1})\n");
+    }
+
+    #[test]
+    #[cfg(feature = "ts-gen")]
+    fn ts_tuples() {
+        let output = generate(TUPLES, "ts", &GeneratorOptions::default());
+
+        assert_lines(&output, 
+            "        pair: [string, number];\n        \
+             triple?: [boolean, number, string];\n");
     }
 }
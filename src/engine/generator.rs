@@ -1,19 +1,161 @@
-use super::tokenizer::TokenSet;
+use std::collections::HashMap;
+
+use super::ast::Literal;
+use super::diagnostics::Diagnostic;
+use super::tokenizer::{TokenSet, TokenType};
 use super::reusability::ReusableDeclarations;
 
+// Options tweaking the output of the generators, read from command-line
+// arguments or from a tc.conf.yaml file.
+#[derive(Debug, Clone)]
+pub struct GeneratorOptions {
+    // Rust: derives `serde` traits on every type and emits the attributes
+    // required to match the other generators serialization.
+    pub serde: bool,
+    // Name of the field holding the constant name of a tagged union once
+    // serialized.
+    pub tag_field: String,
+    // Rust: maps and sets are `BTreeMap`s and `BTreeSet`s instead of
+    // `HashMap`s and `HashSet`s.
+    pub btree_map: bool,
+    // Mappings of well-known types replacing the generator ones, indexed by
+    // the keyword of the type.
+    pub type_mappings: HashMap<String, TypeMapping>,
+    // TypeScript and Go: type of the integers these languages can't hold in
+    // their numbers.
+    pub wide_integers: WideIntegers
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self { 
+            serde: false, 
+            tag_field: "kind".into(), 
+            btree_map: false, 
+            type_mappings: HashMap::new(),
+            wide_integers: WideIntegers::Number
+        }
+    }
+}
+
+// Type integers wider than 53 bits are generated as in TypeScript, 128-bit
+// and big integers being generated as `*big.Int`s in Go unless they're 
+// strings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WideIntegers {
+    // Precision is lost above 2^53.
+    Number,
+    BigInt,
+    // Integers written in base 10.
+    String
+}
+
+impl WideIntegers {
+    // Finds the strategy matching a keyword.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "number" => Some(WideIntegers::Number),
+            "bigint" => Some(WideIntegers::BigInt),
+            "string" => Some(WideIntegers::String),
+            _ => None
+        }
+    }
+}
+
+// Type of the generated language a well-known type is mapped to, along with
+// the imports it requires.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMapping {
+    pub target: String,
+    pub imports: Vec<String>
+}
+
+impl TypeMapping {
+    pub fn new(target: &str, imports: &[&str]) -> Self {
+        Self { 
+            target: target.into(), 
+            imports: imports.iter().map(|import| import.to_string()).collect()
+        }
+    }
+}
+
 // Generates Rust code from TypeCode Tokens.
 #[cfg(feature = "rust-gen")]
 pub trait RustGen {
     fn produce_rs_build_in_single_file(
         source: Vec<TokenSet>, 
         reusability: ReusableDeclarations, 
-        output_path: String) 
-        -> Result<(), String>;
+        output_path: String,
+        options: &GeneratorOptions) 
+        -> Result<(), Diagnostic>;
     fn generate_keyword_from_token_type(token: &TokenSet) -> String;
+    // Names of the annotations the generator acts on, the other ones being
+    // reported as unknown.
+    fn known_annotations() -> &'static [&'static str];
+    // Maps a well-known type to its mapping from the options, or to the 
+    // default one.
+    fn map_well_known_type(token_type: &TokenType, options: &GeneratorOptions)
+        -> TypeMapping;
+    // Qualified names of the declarations implementing `Default`: enums
+    // whose first constant carries no data, aliases of types implementing it
+    // and structs whose fields without default values implement it.
+    fn find_default_declarations(source: &[TokenSet]) -> Vec<String>;
+    // Tells if the type of a token implements `Default`, `defaults` being
+    // the declarations implementing it.
+    fn implements_default(token: &TokenSet, defaults: &[String]) -> bool;
+    // Builds a module and the modules nested in it, `parent_path` being the
+    // path of the module it's nested in and `defaults` the declarations
+    // implementing `Default`.
+    fn build_module(
+        token: &TokenSet,
+        parent_path: &[String],
+        reusability: &ReusableDeclarations,
+        defaults: &[String],
+        options: &GeneratorOptions) -> Result<Vec<String>, Diagnostic>;
     //  Builds a type declaration, only works with inner tokens of 
     //  structs/enums.
     fn build_type_declaration(
-        token: &TokenSet, reusability: &ReusableDeclarations) -> String;
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        module_path: &[String],
+        options: &GeneratorOptions) -> String;
+    // Builds the type of a token, without its name. Local types are referred
+    // to from the module at `module_path`.
+    fn build_type(
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        module_path: &[String],
+        options: &GeneratorOptions) -> String;
+    // Builds the `///` lines documenting a token and its `#[deprecated]`
+    // attribute, prefixed by `indent`.
+    fn build_doc_comment(token: &TokenSet, indent: &str) -> Vec<String>;
+    // Builds the attributes placed above a struct/enum declaration.
+    fn build_attributes(
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Vec<String>;
+    // Builds the `serde_as` type serializing the bytes and durations a token
+    // is made of the way the other generators do, `_` standing for its other
+    // types, or `None` if it holds none. Local types are resolved from the
+    // module at `module_path`.
+    fn build_serde_as_type(
+        token: &TokenSet,
+        reusability: &ReusableDeclarations,
+        module_path: &[String],
+        options: &GeneratorOptions) -> Option<String>;
+    // Builds the functions returning the default values of a struct, along
+    // with its `Default` implementation when every other field implements
+    // `Default` as well.
+    fn build_default_implementation(
+        token: &TokenSet,
+        reusability: &ReusableDeclarations,
+        module_path: &[String],
+        implements_default: bool,
+        options: &GeneratorOptions) -> Vec<String>;
+    // Builds the `validate()` method of a struct with constraints, declared
+    // in the module at `module_path`.
+    fn build_validate_method(token: &TokenSet, module_path: &[String])
+        -> Vec<String>;
 }
 
 // Generates Go code from TypeCode Tokens.
@@ -23,13 +165,55 @@ pub trait GoGen {
         source: Vec<TokenSet>, 
         reusability: ReusableDeclarations,
         output_path: String,
-        output_package_name: String) 
-        -> Result<(), String>;
+        output_package_name: String,
+        options: &GeneratorOptions) 
+        -> Result<(), Diagnostic>;
     fn generate_keyword_from_token_type(token: &TokenSet) -> String;
+    // Names of the annotations the generator acts on, the other ones being
+    // reported as unknown.
+    fn known_annotations() -> &'static [&'static str];
+    // Maps a well-known type to its mapping from the options, or to the 
+    // default one.
+    fn map_well_known_type(token_type: &TokenType, options: &GeneratorOptions)
+        -> TypeMapping;
+    // Builds the declarations of a module and of the modules nested in it,
+    // named after `parent_prefix` and the module name, adding the packages
+    // they use to `imports`.
+    fn build_module(
+        token: &TokenSet,
+        parent_prefix: &str,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions,
+        imports: &mut Vec<String>) -> Result<Vec<String>, Diagnostic>;
     // Builds a type declaration, only works with inner tokens of 
     // structs/enums.
     fn build_type_declaration(
-        token: &TokenSet, reusability: &ReusableDeclarations) -> String;
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> String;
+    // Builds the `//` lines documenting a token, deprecation notice
+    // included, prefixed by `indent`.
+    fn build_doc_comment(token: &TokenSet, indent: &str) -> Vec<String>;
+    // Builds the `New[Module][Struct]()` constructor of a struct with default
+    // values.
+    fn build_constructor(
+        token: &TokenSet,
+        module_name: &str,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Vec<String>;
+    // Builds the `Validate()` method of a struct with constraints, adding the
+    // packages it uses to `imports`.
+    fn build_validate_method(
+        token: &TokenSet,
+        module_name: &str,
+        imports: &mut Vec<String>) -> Vec<String>;
+    // Builds a tagged union enum as a struct pointing to the struct of one
+    // of its constants, along with their JSON (un)marshalling methods.
+    fn build_tagged_union(
+        token: &TokenSet,
+        module_name: &str,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Vec<String>;
 }
 
 // Generates TypeScript code from TypeCode Tokens.
@@ -38,18 +222,73 @@ pub trait TSGen {
     fn produce_ts_build_in_single_file(
         source: Vec<TokenSet>,
         reusability: ReusableDeclarations,
-        output_path: String)
-        -> Result<(), String>;
+        output_path: String,
+        options: &GeneratorOptions)
+        -> Result<(), Diagnostic>;
     fn generate_keyword_from_token_type(token: &TokenSet) -> String;
+    // Names of the annotations the generator acts on, the other ones being
+    // reported as unknown.
+    fn known_annotations() -> &'static [&'static str];
+    // Maps a well-known type to its mapping from the options, or to the 
+    // default one.
+    fn map_well_known_type(token_type: &TokenType, options: &GeneratorOptions)
+        -> TypeMapping;
+    // Builds a namespace out of a module and the modules nested in it.
+    fn build_module(
+        token: &TokenSet,
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> Result<Vec<String>, Diagnostic>;
     // Builds a type declaration, only works with inner tokens of
     // structs/enums.
     fn build_type_declaration(
-        token: &TokenSet, reusability: &ReusableDeclarations) -> String;
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> String;
+    // Builds the type of a token, without its name.
+    fn build_type(
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations,
+        options: &GeneratorOptions) -> String;
+    // Name of the property holding a field, the name it's serialized as when
+    // it has one.
+    fn build_property_name(token: &TokenSet) -> String;
+    // Builds the key of the property holding a field, quoted when its name
+    // isn't an identifier.
+    fn build_property_key(token: &TokenSet) -> String;
+    // Builds the `/** */` JSDoc block documenting a token, `@deprecated` tag
+    // included, prefixed by `indent`.
+    fn build_doc_comment(token: &TokenSet, indent: &str) -> Vec<String>;
+    // Builds a value of type `value_type`, wide integers being written as 
+    // the type they're generated as.
+    fn build_value(
+        value_type: &TokenSet, 
+        value: &Literal, 
+        options: &GeneratorOptions) -> String;
+    // Builds the `default[Struct]()` factory of a struct with default values,
+    // taking every other field.
+    fn build_default_factory(token: &TokenSet, options: &GeneratorOptions)
+        -> Vec<String>;
+    // Builds the `validate[Struct]()` function of a struct with constraints.
+    fn build_validator(token: &TokenSet, options: &GeneratorOptions)
+        -> Vec<String>;
+    // Builds a tagged union enum as a discriminated union type.
+    fn build_tagged_union(
+        token: &TokenSet, 
+        reusability: &ReusableDeclarations, 
+        options: &GeneratorOptions) -> Vec<String>;
 }
 
 // Generates Rust reusability statements.
 #[cfg(feature = "rust-gen")]
 pub trait RustReusability {
+    // Refers to the declaration from the module at `from_module`.
+    fn produce_reusable_statement_from_struct_or_enum_token(
+        &self, from_module: &[String]) -> String;
+}
+
+// Generates Go reusability statements.
+#[cfg(feature = "go-gen")]
+pub trait GoReusability {
     fn produce_reusable_statement_from_struct_or_enum_token(&self) -> String;
 }
 
@@ -0,0 +1,262 @@
+use super::diagnostics::{Diagnostic, DiagnosticKind, Diagnostics, SourceSpan};
+use crate::debug;
+
+// Kind of a lexeme, the smallest meaningful piece of a TypeCode file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexemeKind {
+    // Keywords, types and names: `[A-Za-z_][A-Za-z0-9_]*`.
+    Word(String),
+    // Integer, decimal or exponent numbers, kept as written.
+    Number(String),
+    // Double quoted strings, with escape sequences resolved.
+    Text(String),
+    // Any single punctuation character such as `=`, `<` or `,`.
+    Symbol(char),
+    // Content of a `::` line, documenting the declaration below it.
+    DocComment(String),
+    Newline
+}
+
+// A lexeme along with the place of the source it has been read from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexeme {
+    pub kind: LexemeKind,
+    pub span: SourceSpan
+}
+
+// Characters read as `LexemeKind::Symbol`.
+const SYMBOLS: &str = "=<>,()[]{}.@:-";
+
+// Splits a TypeCode file into lexemes. Whitespaces (including `\r`) only
+// separate lexemes, and comments are dropped: a comment starts with a `:` that
+// is either the first character of a line or preceded by a whitespace outside
+// of parentheses, and ends with the line. Lines starting with `::` are doc
+// comments and are kept.
+pub fn tokenize(source: &str, file: &str, diagnostics: &mut Diagnostics)
+    -> Vec<Lexeme> {
+    let mut output: Vec<Lexeme> = vec![];
+
+    for (index, line) in source.split('\n').enumerate() {
+        let line_number = index + 1;
+        let chars = line.chars().collect::<Vec<char>>();
+        let mut pos = 0;
+        // Parentheses opened on the line, within which `:` separates the name
+        // of an annotation argument from its value.
+        let mut depth: usize = 0;
+
+        while pos < chars.len() {
+            let current = chars[pos];
+            let start = pos;
+            let span = |end: usize| SourceSpan::new(
+                file, line_number, start + 1, end - start);
+
+            if current.is_whitespace() {
+                pos += 1;
+            } else if current == ':' && chars.get(pos + 1) == Some(&':') &&
+                chars[..pos].iter().all(|c| c.is_whitespace()) {
+                let text = chars[pos + 2..].iter().collect::<String>();
+                let text = text.strip_prefix(' ').unwrap_or(&text).trim_end();
+
+                output.push(Lexeme {
+                    kind: LexemeKind::DocComment(text.to_string()),
+                    span: span(chars.len()) });
+                break;
+            } else if current == ':' && depth == 0 &&
+                (pos == 0 || chars[pos - 1].is_whitespace()) {
+                break;
+            } else if current.is_ascii_alphabetic() || current == '_' {
+                while pos < chars.len() &&
+                    (chars[pos].is_ascii_alphanumeric() || chars[pos] == '_') {
+                    pos += 1;
+                }
+                output.push(Lexeme {
+                    kind: LexemeKind::Word(chars[start..pos].iter().collect()),
+                    span: span(pos) });
+            } else if current.is_ascii_digit() {
+                while pos < chars.len() &&
+                    (chars[pos].is_ascii_digit() || (chars[pos] == '.' &&
+                     chars.get(pos + 1).is_some_and(|c| c.is_ascii_digit()))) {
+                    pos += 1;
+                }
+                // An exponent is only read when digits follow it, `e` being
+                // a name otherwise.
+                if matches!(chars.get(pos), Some('e' | 'E')) {
+                    let sign = matches!(chars.get(pos + 1), Some('+' | '-'));
+                    let digits = pos + 1 + sign as usize;
+
+                    if chars.get(digits).is_some_and(|c| c.is_ascii_digit()) {
+                        pos = digits;
+                        while pos < chars.len() && chars[pos].is_ascii_digit() {
+                            pos += 1;
+                        }
+                    }
+                }
+                output.push(Lexeme {
+                    kind: LexemeKind::Number(
+                        chars[start..pos].iter().collect()),
+                    span: span(pos) });
+            } else if current == '"' {
+                let mut text = String::new();
+                let mut terminated = false;
+
+                pos += 1;
+                while pos < chars.len() {
+                    match chars[pos] {
+                        '"' => { terminated = true; pos += 1; break; }
+                        '\\' if pos + 1 < chars.len() => {
+                            text.push(match chars[pos + 1] {
+                                'n' => '\n',
+                                't' => '\t',
+                                other => other
+                            });
+                            pos += 2;
+                        }
+                        other => { text.push(other); pos += 1; }
+                    }
+                }
+
+                if !terminated {
+                    diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::UnterminatedString, Some(span(pos))));
+                }
+                output.push(Lexeme {
+                    kind: LexemeKind::Text(text), span: span(pos) });
+            } else if SYMBOLS.contains(current) {
+                match current {
+                    '(' => depth += 1,
+                    ')' => depth = depth.saturating_sub(1),
+                    _ => ()
+                }
+                pos += 1;
+                output.push(Lexeme {
+                    kind: LexemeKind::Symbol(current), span: span(pos) });
+            } else {
+                pos += 1;
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::UnexpectedCharacter(current),
+                    Some(span(pos))));
+            }
+        }
+
+        output.push(Lexeme {
+            kind: LexemeKind::Newline,
+            span: SourceSpan::new(file, line_number, chars.len() + 1, 0) });
+    }
+
+    debug!("Lexemes of {}: {:?}", file, output);
+
+    return output;
+}
+
+impl LexemeKind {
+    // Describes the lexeme the way it is written, to be used in diagnostics.
+    pub fn describe(&self) -> String {
+        match self {
+            LexemeKind::Word(word) => format!("`{}`", word),
+            LexemeKind::Number(number) => format!("`{}`", number),
+            LexemeKind::Text(text) => format!("\"{}\"", text),
+            LexemeKind::Symbol(symbol) => format!("`{}`", symbol),
+            LexemeKind::DocComment(_) => "doc comment".into(),
+            LexemeKind::Newline => "end of line".into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<LexemeKind> {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(source, "test.tc", &mut diagnostics);
+
+        assert!(diagnostics.entries.is_empty(), "{:?}", diagnostics.entries);
+        return lexemes.into_iter().map(|lexeme| lexeme.kind).collect();
+    }
+
+    fn word(name: &str) -> LexemeKind {
+        LexemeKind::Word(name.into())
+    }
+
+    #[test]
+    fn crlf_and_tabs_are_blank() {
+        assert_eq!(kinds("struct A\r\n\tstring\t Name\r\n"), vec![
+            word("struct"), word("A"), LexemeKind::Newline,
+            word("string"), word("Name"), LexemeKind::Newline,
+            LexemeKind::Newline]);
+    }
+
+    #[test]
+    fn spans_count_tabs_as_one_column() {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize("\t\tstring Name", "test.tc", &mut diagnostics);
+
+        assert_eq!(lexemes[0].span, SourceSpan::new("test.tc", 1, 3, 6));
+        assert_eq!(lexemes[1].span, SourceSpan::new("test.tc", 1, 10, 4));
+    }
+
+    #[test]
+    fn doc_comments_are_kept_and_comments_dropped() {
+        assert_eq!(kinds(":: Documented.\n: Dropped.\n  :: Indented.\n"), vec![
+            LexemeKind::DocComment("Documented.".into()), LexemeKind::Newline,
+            LexemeKind::Newline,
+            LexemeKind::DocComment("Indented.".into()), LexemeKind::Newline,
+            LexemeKind::Newline]);
+    }
+
+    #[test]
+    fn trailing_comments_follow_a_blank() {
+        assert_eq!(kinds("string Name : comment\nstring Other\t:: comment"), 
+            vec![word("string"), word("Name"), LexemeKind::Newline,
+                 word("string"), word("Other"), LexemeKind::Newline]);
+    }
+
+    #[test]
+    fn colons_after_a_name_are_separators() {
+        assert_eq!(kinds("@column(name: \"id\")"), vec![
+            LexemeKind::Symbol('@'), word("column"), LexemeKind::Symbol('('),
+            word("name"), LexemeKind::Symbol(':'), 
+            LexemeKind::Text("id".into()), LexemeKind::Symbol(')'),
+            LexemeKind::Newline]);
+    }
+
+    #[test]
+    fn colons_within_parentheses_are_separators() {
+        assert_eq!(kinds("@rename(json : \"id\") : comment"), vec![
+            LexemeKind::Symbol('@'), word("rename"), LexemeKind::Symbol('('),
+            word("json"), LexemeKind::Symbol(':'), 
+            LexemeKind::Text("id".into()), LexemeKind::Symbol(')'),
+            LexemeKind::Newline]);
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(kinds("12 1.5 1e5 2.5E-3 1e+2 3.x 4e"), vec![
+            LexemeKind::Number("12".into()), LexemeKind::Number("1.5".into()),
+            LexemeKind::Number("1e5".into()), 
+            LexemeKind::Number("2.5E-3".into()),
+            LexemeKind::Number("1e+2".into()), 
+            LexemeKind::Number("3".into()), LexemeKind::Symbol('.'), 
+            word("x"), 
+            LexemeKind::Number("4".into()), word("e"),
+            LexemeKind::Newline]);
+    }
+
+    #[test]
+    fn text_escapes() {
+        assert_eq!(kinds(r#""a\"b\\c\nd\te""#), vec![
+            LexemeKind::Text("a\"b\\c\nd\te".into()), LexemeKind::Newline]);
+    }
+
+    #[test]
+    fn errors() {
+        let mut diagnostics = Diagnostics::default();
+        tokenize("\"open\nstring $Name", "test.tc", &mut diagnostics);
+
+        assert_eq!(diagnostics.entries.iter()
+            .map(|diagnostic| diagnostic.kind.clone())
+            .collect::<Vec<DiagnosticKind>>(), 
+            vec![DiagnosticKind::UnterminatedString, 
+                 DiagnosticKind::UnexpectedCharacter('$')]);
+    }
+}
@@ -2,3 +2,7 @@ pub mod reusability;
 pub mod generator;
 pub mod tokenizer;
 pub mod ext;
+pub mod diagnostics;
+pub mod lexer;
+pub mod parser;
+pub mod ast;
@@ -0,0 +1,1455 @@
+use super::ast::{
+    Alias, Annotation, AnnotationArgument, Const, Constraint, Enum, Extern,
+    ExternMapping, Field, FieldGroup, Ident, Item, Literal, Member, Module,
+    Struct, TypeRef, Variant, VariantPayload};
+use super::diagnostics::{Diagnostic, DiagnosticKind, Diagnostics, SourceSpan};
+use super::lexer::{Lexeme, LexemeKind};
+use super::tokenizer::{TokenParameter, TokenType};
+use crate::debug;
+
+// Keywords starting a declaration, they end the declaration preceding them.
+const DECLARATION_KEYWORDS: [&str; 8] = 
+    ["module", "struct", "enum", "alias", "newtype", "const", "extern", 
+     "fields"];
+
+// Languages extern types can be mapped to.
+const EXTERN_LANGUAGES: [&str; 3] = ["rs", "go", "ts"];
+
+// Constraints that can be written after a field.
+const CONSTRAINTS: [&str; 5] = ["min", "max", "length", "pattern", "non_empty"];
+
+// Recursive descent parser building modules out of the lexemes of a file.
+// The grammar is line based:
+//
+// file     := module*
+// (doc)    := ("::" text NEWLINE)*
+// (annotations) := ("@" name ("(" argument ("," argument)* ")")?)+ NEWLINE
+// argument := (name ":")? literal
+// module   := "module" name ("." name)* NEWLINE item*
+// item     := struct | enum | alias | const | extern | group
+// struct   := "struct" name ("<" name ("," name)* ">")? 
+//             ("extends" path ("," path)*)? NEWLINE member*
+// group    := "fields" name NEWLINE member*
+// member   := field | "include" path NEWLINE
+// enum     := "enum" name NEWLINE variant*
+// alias    := ("alias" | "newtype") name "=" type parameter* NEWLINE
+// const    := "const" name name "=" literal NEWLINE
+// extern   := "extern" name NEWLINE mapping*
+// mapping  := name text ("import" text ("," text)*)? NEWLINE
+// field    := type parameter* name ("=" literal)? constraints? NEWLINE
+// constraints := "[" constraint ("," constraint)* "]"
+// constraint  := name ("(" literal ("," literal)* ")")?
+// type     := path ("<" type parameter* ("," type parameter*)* ">")?
+//           | "(" type parameter* ("," type parameter*)+ ")"
+// path     := name ("." name)*
+// variant  := name ("=" literal | "(" path ")" | "{" fields "}")? NEWLINE
+// fields   := inline ("," inline)* ","?
+// inline   := ("@" name ("(" argument ("," argument)* ")")?)* 
+//             type parameter* name
+// literal  := "-"? number | text | "true" | "false"
+//
+// Every node can be preceded by `::` doc comment lines and by annotation
+// lines, which are attached to it. When a line can't be parsed, the error is
+// reported and parsing resumes on the next line.
+pub struct Parser<'a> {
+    lexemes: &'a [Lexeme],
+    pos: usize,
+    diagnostics: &'a mut Diagnostics,
+    // Doc comments read since the last node.
+    pending_docs: Vec<String>,
+    // Annotations read since the last node.
+    pending_annotations: Vec<Annotation>
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(lexemes: &'a [Lexeme], diagnostics: &'a mut Diagnostics)
+        -> Self {
+        Self { 
+            lexemes, pos: 0, diagnostics, pending_docs: vec![], 
+            pending_annotations: vec![] }
+    }
+
+    // Parses every module of the file.
+    pub fn parse_file(&mut self) -> Vec<Module> {
+        let mut output = vec![];
+
+        loop {
+            self.skip_newlines();
+
+            match self.peek_keyword() {
+                None if self.at_eof() => break,
+                Some("module") => match self.parse_module() {
+                    Ok(module) => output.push(module),
+                    Err(diagnostic) => self.recover(diagnostic)
+                },
+                // Declarations are still parsed to report errors about their
+                // content, but are left out of the output.
+                Some(keyword) => {
+                    let token_type = declaration_token_type(keyword);
+
+                    match self.parse_item() {
+                        Ok(item) => self.diagnostics.push(Diagnostic::error(
+                            DiagnosticKind::DeclarationOutsideModule(
+                                token_type),
+                            Some(item.span().clone()))
+                            .with_note("declare a `module` first")),
+                        Err(diagnostic) => self.recover(diagnostic)
+                    }
+                }
+                None => {
+                    let span = self.line_span();
+                    self.recover(Diagnostic::error(
+                        DiagnosticKind::FieldOutsideDeclaration, Some(span))
+                        .with_note("declare a `struct` or an `enum` first"));
+                }
+            }
+        }
+
+        debug!("Parsed modules: {:#?}", output);
+
+        return output;
+    }
+
+    fn parse_module(&mut self) -> Result<Module, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let keyword = self.advance().span.clone();
+
+        if self.peek() == Some(&LexemeKind::Newline) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::MissingDeclarationName(TokenType::Module),
+                Some(keyword)));
+        }
+
+        // Nested modules are named after the modules they are nested in.
+        let mut path = vec![self.expect_word("a name")?];
+
+        while self.peek() == Some(&LexemeKind::Symbol('.')) {
+            self.pos += 1;
+            path.push(self.expect_word("a module name")?);
+        }
+        let span = keyword.to(&path.last().unwrap().span);
+        self.expect_newline()?;
+
+        let mut items = vec![];
+
+        loop {
+            self.skip_newlines();
+
+            match self.peek_keyword() {
+                None if self.at_eof() => break,
+                Some("module") => break,
+                Some(_) => match self.parse_item() {
+                    Ok(item) => items.push(item),
+                    Err(diagnostic) => self.recover(diagnostic)
+                },
+                None => {
+                    let span = self.line_span();
+                    self.recover(Diagnostic::error(
+                        DiagnosticKind::FieldOutsideDeclaration, Some(span))
+                        .with_note("declare a `struct` or an `enum` first"));
+                }
+            }
+        }
+
+        Ok(Module { path, docs, annotations, items, span })
+    }
+
+    fn parse_item(&mut self) -> Result<Item, Diagnostic> {
+        match self.peek_keyword() {
+            Some("struct") => Ok(Item::Struct(self.parse_struct()?)),
+            Some("enum") => Ok(Item::Enum(self.parse_enum()?)),
+            Some("alias") => Ok(Item::Alias(
+                self.parse_alias(TokenType::Alias)?)),
+            Some("newtype") => Ok(Item::NewType(
+                self.parse_alias(TokenType::NewType)?)),
+            Some("const") => Ok(Item::Const(self.parse_const()?)),
+            Some("extern") => Ok(Item::Extern(self.parse_extern()?)),
+            Some("fields") => Ok(Item::FieldGroup(self.parse_field_group()?)),
+            _ => Err(self.unexpected("a declaration"))
+        }
+    }
+
+    fn parse_struct(&mut self) -> Result<Struct, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let (name, type_parameters, bases, span) = 
+            self.parse_declaration_header(TokenType::Structure)?;
+        let mut members = vec![];
+
+        self.parse_members(|parser| {
+            members.push(parser.parse_member()?);
+            Ok(())
+        });
+
+        Ok(Struct { 
+            name, type_parameters, bases, docs, annotations, members, span })
+    }
+
+    fn parse_field_group(&mut self) -> Result<FieldGroup, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let (name, _, _, span) = self.parse_declaration_header(
+            TokenType::FieldGroup)?;
+        let mut members = vec![];
+
+        self.parse_members(|parser| {
+            members.push(parser.parse_member()?);
+            Ok(())
+        });
+
+        Ok(FieldGroup { name, docs, annotations, members, span })
+    }
+
+    // Parses a field, or `include [name]` pulling the fields of a field 
+    // group, which can't be documented.
+    fn parse_member(&mut self) -> Result<Member, Diagnostic> {
+        if self.peek() != Some(&LexemeKind::Word("include".into())) {
+            return Ok(Member::Field(Box::new(self.parse_field()?)));
+        }
+
+        self.pending_docs.clear();
+        self.pending_annotations.clear();
+        let keyword = self.advance().span.clone();
+        let name = self.parse_qualified_name("a field group")?;
+        self.expect_newline()?;
+
+        Ok(Member::Include(Ident { 
+            span: keyword.to(&name.span), name: name.name }))
+    }
+
+    fn parse_enum(&mut self) -> Result<Enum, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let (name, _, _, span) = self.parse_declaration_header(
+            TokenType::Enumeration)?;
+        let mut variants = vec![];
+
+        self.parse_members(|parser| {
+            variants.push(parser.parse_variant()?);
+            Ok(())
+        });
+        self.check_enum_values(&variants);
+
+        Ok(Enum { name, docs, annotations, variants, span })
+    }
+
+    fn parse_alias(&mut self, token_type: TokenType)
+        -> Result<Alias, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let keyword = self.advance().span.clone();
+
+        if self.peek() == Some(&LexemeKind::Newline) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::MissingDeclarationName(token_type),
+                Some(keyword)));
+        }
+
+        let name = self.expect_word("a name")?;
+        self.expect_symbol('=')?;
+
+        let mut target = self.parse_type()?;
+        let words = self.parse_words();
+        let span = keyword.to(words.last().map(|word| &word.span)
+            .unwrap_or(&target.span));
+        target.parameters = parameters_from_words(words)?;
+        self.resolve_floated(&mut target)?;
+        self.expect_newline()?;
+
+        Ok(Alias { name, docs, annotations, target, span })
+    }
+
+    fn parse_const(&mut self) -> Result<Const, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let keyword = self.advance().span.clone();
+
+        if self.peek() == Some(&LexemeKind::Newline) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::MissingDeclarationName(TokenType::Constant),
+                Some(keyword)));
+        }
+
+        let const_type = self.expect_word("a type")?;
+        let name = self.expect_word("a name")?;
+        self.expect_symbol('=')?;
+
+        let mut value = self.parse_literal()?;
+        self.check_value_type(
+            &const_type, &mut value, DiagnosticKind::NonPrimitiveConst)?;
+        self.expect_newline()?;
+
+        let span = keyword.to(&value.1);
+        Ok(Const { name, docs, annotations, const_type, value, span })
+    }
+
+    fn parse_extern(&mut self) -> Result<Extern, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let (name, _, _, span) = self.parse_declaration_header(
+            TokenType::Extern)?;
+        let mut mappings = vec![];
+
+        self.parse_members(|parser| {
+            mappings.push(parser.parse_extern_mapping()?);
+            Ok(())
+        });
+
+        Ok(Extern { name, docs, annotations, mappings, span })
+    }
+
+    fn parse_extern_mapping(&mut self) -> Result<ExternMapping, Diagnostic> {
+        // Mappings aren't documented, only the extern type is.
+        self.take_docs();
+        self.take_annotations();
+
+        let language = self.expect_word("a language")?;
+
+        if !EXTERN_LANGUAGES.contains(&language.name.as_str()) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::UnknownExternLanguage(language.name), 
+                Some(language.span))
+                .with_note("extern types can be mapped to `rs`, `go` and \
+                           `ts`"));
+        }
+
+        let (target, mut span) = self.expect_text("a type")?;
+        let mut imports = vec![];
+        span = language.span.to(&span);
+
+        if self.peek() == Some(&LexemeKind::Word("import".into())) {
+            self.pos += 1;
+
+            loop {
+                let (import, import_span) = self.expect_text("an import")?;
+                imports.push(import);
+                span = span.to(&import_span);
+
+                if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                self.pos += 1;
+            }
+        }
+        self.expect_newline()?;
+
+        Ok(ExternMapping { language, target, imports, span })
+    }
+
+    // Checks that the value of a constant, the default value of a field or a
+    // bound fits in its type, `non_primitive` being reported for types that
+    // can't be given values. Integers given to floats are turned into floats.
+    fn check_value_type(
+        &self, 
+        value_type: &Ident, 
+        value: &mut (Literal, SourceSpan),
+        non_primitive: impl Fn(String) -> DiagnosticKind)
+        -> Result<(), Diagnostic> {
+        let token_type = TokenType::from_keyword(&value_type.name);
+        let range = match token_type {
+            TokenType::IntU8 => Some((0, u8::MAX as i128)),
+            TokenType::IntU16 => Some((0, u16::MAX as i128)),
+            TokenType::IntU32 => Some((0, u32::MAX as i128)),
+            TokenType::IntU64 => Some((0, u64::MAX as i128)),
+            TokenType::IntI8 => Some((i8::MIN as i128, i8::MAX as i128)),
+            TokenType::IntI16 => Some((i16::MIN as i128, i16::MAX as i128)),
+            TokenType::IntI32 => Some((i32::MIN as i128, i32::MAX as i128)),
+            TokenType::IntI64 => Some((i64::MIN as i128, i64::MAX as i128)),
+            _ => None
+        };
+
+        let is_valid = match (&token_type, &value.0) {
+            // Go holds 128-bit and big integers in `*big.Int`s, which can't
+            // be built from literals.
+            (token_type, _) if token_type.is_big_integer() =>
+                return Err(Diagnostic::error(
+                    non_primitive(value_type.name.clone()),
+                    Some(value_type.span.clone()))
+                    .with_note("128-bit and big integers can't be written \
+                               as literals in every language")),
+            (token_type, _) if token_type.is_well_known() ||
+                matches!(token_type, TokenType::Custom | TokenType::Map 
+                         | TokenType::Vector | TokenType::Array 
+                         | TokenType::Set | TokenType::Tuple) =>
+                return Err(Diagnostic::error(
+                    non_primitive(value_type.name.clone()),
+                    Some(value_type.span.clone()))),
+            (TokenType::String, Literal::Text(_)) => true,
+            (TokenType::Char, Literal::Text(text)) => text.chars().count() == 1,
+            (TokenType::Bool, Literal::Bool(_)) => true,
+            (TokenType::Float32 | TokenType::Float64, Literal::Float(_)) => 
+                true,
+            (TokenType::Float32 | TokenType::Float64, Literal::Integer(number)) 
+                => {
+                value.0 = Literal::Float(*number as f64);
+                true
+            }
+            (_, Literal::Integer(number)) => range
+                .is_some_and(|(min, max)| (min..=max).contains(number)),
+            _ => false
+        };
+
+        if !is_valid {
+            return Err(Diagnostic::error(
+                DiagnosticKind::MismatchedValue { 
+                    value: value.0.to_string(), 
+                    type_name: value_type.name.clone() },
+                Some(value.1.clone())));
+        }
+        Ok(())
+    }
+
+    fn parse_field(&mut self) -> Result<Field, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let mut field = self.parse_field_body()?;
+
+        // Only primitive values, optional or not, can have defaults.
+        if self.peek() == Some(&LexemeKind::Symbol('=')) {
+            self.pos += 1;
+
+            let mut value = self.parse_literal()?;
+            let field_type = &field.field_type;
+
+            if let Some((_, span)) = field_type.parameters.iter()
+                .find(|(parameter, _)| *parameter != TokenParameter::Optional) {
+                return Err(Diagnostic::error(
+                    DiagnosticKind::UnsupportedDefault(
+                        type_description(field_type)),
+                    Some(field_type.name.span.to(span))));
+            }
+            self.check_value_type(
+                &field_type.name, &mut value, 
+                DiagnosticKind::UnsupportedDefault)?;
+
+            field.span = field.span.to(&value.1);
+            field.default = Some(value);
+        }
+
+        if self.peek() == Some(&LexemeKind::Symbol('[')) {
+            self.pos += 1;
+
+            loop {
+                let constraint = self.parse_constraint(&field.field_type)?;
+                field.constraints.push(constraint);
+
+                if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                self.pos += 1;
+            }
+            field.span = field.span.to(&self.expect_symbol(']')?);
+        }
+        self.expect_newline()?;
+
+        Ok(Field { docs, annotations, ..field })
+    }
+
+    // Parses a field up to its name, as fields are either ended by a new line
+    // or by a separator when inlined.
+    fn parse_field_body(&mut self) -> Result<Field, Diagnostic> {
+        let mut field_type = self.parse_type()?;
+        let mut words = self.parse_words();
+
+        let name = words.pop().ok_or(Diagnostic::error(
+            DiagnosticKind::MissingFieldName, Some(field_type.span.clone())))?;
+        field_type.parameters = parameters_from_words(words)?;
+        self.resolve_floated(&mut field_type)?;
+        let span = field_type.span.to(&name.span);
+
+        Ok(Field { 
+            field_type, name, docs: vec![], annotations: vec![], default: None, 
+            constraints: vec![], 
+            span })
+    }
+
+    // Parses `[name]` or `[name]([value], [value])`, checking that the
+    // constraint can be applied to a field of type `field_type` and that it
+    // is given the values it takes.
+    fn parse_constraint(&mut self, field_type: &TypeRef)
+        -> Result<(Constraint, SourceSpan), Diagnostic> {
+        let name = self.expect_word("a constraint")?;
+        let mut span = name.span.clone();
+        let mut arguments = vec![];
+
+        if self.peek() == Some(&LexemeKind::Symbol('(')) {
+            self.pos += 1;
+
+            loop {
+                arguments.push(self.parse_literal()?);
+
+                if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                self.pos += 1;
+            }
+            span = span.to(&self.expect_symbol(')')?);
+        }
+
+        if !CONSTRAINTS.contains(&name.name.as_str()) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::UnknownConstraint(name.name), Some(name.span))
+                .with_note("available constraints are `min`, `max`, \
+                           `length`, `pattern` and `non_empty`"));
+        }
+        if !applicable_constraints(field_type).contains(&name.name.as_str()) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::InapplicableConstraint { 
+                    constraint: name.name, 
+                    type_name: type_description(field_type) },
+                Some(span)));
+        }
+
+        let invalid_arguments = |expected: &str| Diagnostic::error(
+            DiagnosticKind::InvalidConstraintArguments { 
+                constraint: name.name.clone(), expected: expected.into() },
+            Some(span.clone()));
+        let constraint = match (name.name.as_str(), arguments.as_mut_slice()) {
+            // Bounds are only applied to numbers, which are always primitive.
+            ("min" | "max", [value]) => {
+                self.check_value_type(
+                    &field_type.name, value, 
+                    |type_name| DiagnosticKind::InapplicableConstraint { 
+                        constraint: name.name.clone(), type_name })?;
+
+                if name.name == "min" { Constraint::Min(value.0.clone()) } 
+                else { Constraint::Max(value.0.clone()) }
+            }
+            ("length", 
+             [(Literal::Integer(min), _), (Literal::Integer(max), _)]) 
+                if 0 <= *min && *min <= *max && 
+                    *max <= u64::MAX as i128 => 
+                Constraint::Length(*min as u64, *max as u64),
+            ("pattern", [(Literal::Text(pattern), pattern_span)]) => {
+                // Patterns are checked with the parser of the `regex` crate,
+                // so that the generated Rust code can't fail to compile them.
+                if let Err(error) = regex_syntax::Parser::new().parse(pattern) {
+                    let reason = match &error {
+                        regex_syntax::Error::Parse(error) => 
+                            error.kind().to_string(),
+                        regex_syntax::Error::Translate(error) => 
+                            error.kind().to_string(),
+                        error => error.to_string()
+                    };
+
+                    return Err(Diagnostic::error(
+                        DiagnosticKind::InvalidPattern(reason), 
+                        Some(pattern_span.clone())));
+                }
+                Constraint::Pattern(pattern.clone())
+            }
+            ("non_empty", []) => Constraint::NonEmpty,
+            ("min" | "max", _) => 
+                return Err(invalid_arguments("a single number")),
+            ("length", _) => return Err(invalid_arguments(
+                "a minimum and a maximum, non-negative integers with the \
+                 minimum being the lowest")),
+            ("pattern", _) => 
+                return Err(invalid_arguments("a single string")),
+            _ => return Err(invalid_arguments("no value"))
+        };
+
+        Ok((constraint, span))
+    }
+
+    // Parses a type and its type arguments, parameters being left to the
+    // caller as the name of a field can't be told apart from them before the
+    // end of the line.
+    fn parse_type(&mut self) -> Result<TypeRef, Diagnostic> {
+        if self.peek() == Some(&LexemeKind::Symbol('(')) {
+            return self.parse_tuple();
+        }
+
+        let name = self.parse_qualified_name("a type")?;
+
+        // Tuples have no name, `tuple` only being the keyword of their type.
+        if TokenType::from_keyword(&name.name) == TokenType::Tuple {
+            return Err(Diagnostic::error(
+                DiagnosticKind::UnexpectedToken { 
+                    expected: "a type".into(), found: "`tuple`".into() },
+                Some(name.span))
+                .with_note("tuples are written as `(T, U)`"));
+        }
+        let mut span = name.span.clone();
+        let mut arguments = vec![];
+        let mut array_length = None;
+        let is_array = TokenType::from_keyword(&name.name) == TokenType::Array;
+
+        if self.peek() == Some(&LexemeKind::Symbol('<')) {
+            self.pos += 1;
+
+            loop {
+                // The length of an array follows the type of its items.
+                if is_array && arguments.len() == 1 {
+                    array_length = Some(self.parse_array_length()?);
+                    break;
+                }
+
+                arguments.push(self.parse_type_argument()?);
+
+                if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                self.pos += 1;
+            }
+            if is_array && array_length.is_none() {
+                return Err(self.unexpected("an array length")
+                    .with_note("arrays are written as `array<T, N>`"));
+            }
+            span = span.to(&self.expect_symbol('>')?);
+        }
+
+        // Built-in types made of other types take a fixed number of type
+        // arguments, while the ones of custom types are checked once every
+        // declaration is known.
+        let expected = match TokenType::from_keyword(&name.name) {
+            TokenType::Map => 2,
+            TokenType::Vector | TokenType::Array | TokenType::Set => 1,
+            TokenType::Custom => arguments.len(),
+            _ => 0
+        };
+        if arguments.len() != expected {
+            return Err(Diagnostic::error(
+                DiagnosticKind::TypeArgumentCount { 
+                    type_name: name.name, expected, found: arguments.len() },
+                Some(span)));
+        }
+
+        Ok(TypeRef { name, arguments, array_length, parameters: vec![], span })
+    }
+
+    // Parses a type given to another one, followed by its parameters.
+    fn parse_type_argument(&mut self) -> Result<TypeRef, Diagnostic> {
+        let mut argument = self.parse_type()?;
+        let words = self.parse_words();
+
+        argument.parameters = parameters_from_words(words)?;
+        self.resolve_floated(&mut argument)?;
+        if let Some((_, last)) = argument.parameters.last() {
+            argument.span = argument.span.to(last);
+        }
+        Ok(argument)
+    }
+
+    // Parses `([type], [type])`, tuples being made of two items or more which
+    // are stored as the type arguments of a `tuple` type.
+    fn parse_tuple(&mut self) -> Result<TypeRef, Diagnostic> {
+        let start = self.expect_symbol('(')?;
+        let mut items = vec![self.parse_type_argument()?];
+
+        while self.peek() == Some(&LexemeKind::Symbol(',')) {
+            self.pos += 1;
+            items.push(self.parse_type_argument()?);
+        }
+        if items.len() < 2 {
+            return Err(self.unexpected("`,`")
+                .with_note("tuples are made of two items or more"));
+        }
+        let span = start.to(&self.expect_symbol(')')?);
+
+        Ok(TypeRef { 
+            name: Ident { name: "tuple".into(), span: span.clone() }, 
+            arguments: items, 
+            array_length: None, 
+            parameters: vec![], 
+            span })
+    }
+
+    fn parse_array_length(&mut self) -> Result<usize, Diagnostic> {
+        let (length, span) = self.parse_literal()?;
+
+        match length {
+            Literal::Integer(length) if length > 0 && 
+                length <= usize::MAX as i128 => Ok(length as usize),
+            _ => Err(Diagnostic::error(
+                DiagnosticKind::InvalidArrayLength(length.to_string()), 
+                Some(span))
+                .with_note("array lengths are positive integers"))
+        }
+    }
+
+    // Replaces the deprecated `[integer] floated` spelling with the float
+    // type of the same size, warning about it.
+    fn resolve_floated(&mut self, type_ref: &mut TypeRef)
+        -> Result<(), Diagnostic> {
+        let position = type_ref.parameters.iter()
+            .position(|(parameter, _)| *parameter == TokenParameter::Floated);
+        let (_, span) = match position {
+            Some(position) => type_ref.parameters.remove(position),
+            None => return Ok(())
+        };
+
+        let float_type = match TokenType::from_keyword(&type_ref.name.name) {
+            TokenType::IntU8 | TokenType::IntI8 | TokenType::IntU16 
+                | TokenType::IntI16 | TokenType::IntU32 
+                | TokenType::IntI32 => "float_32",
+            TokenType::IntU64 | TokenType::IntI64 => "float_64",
+            _ => return Err(Diagnostic::error(
+                DiagnosticKind::FloatedOnNonInteger(
+                    type_ref.name.name.clone()),
+                Some(span)))
+        };
+
+        self.diagnostics.push(Diagnostic::warning(
+            DiagnosticKind::DeprecatedFloated, Some(span))
+            .with_note(format!("use `{}` instead of `{} floated`", 
+                               float_type, type_ref.name.name)));
+        type_ref.name.name = float_type.into();
+        Ok(())
+    }
+
+    // Parses a name that can be qualified with the path of the module it's
+    // declared in, such as `Billing.Invoices.Invoice`.
+    fn parse_qualified_name(&mut self, expected: &str) 
+        -> Result<Ident, Diagnostic> {
+        let mut name = self.expect_word(expected)?;
+
+        while self.peek() == Some(&LexemeKind::Symbol('.')) {
+            self.pos += 1;
+
+            let segment = self.expect_word("a name")?;
+            name.name = format!("{}.{}", name.name, segment.name);
+            name.span = name.span.to(&segment.span);
+        }
+        return Ok(name);
+    }
+
+    // Reads the words following the current lexeme.
+    fn parse_words(&mut self) -> Vec<Ident> {
+        let mut words = vec![];
+
+        while let Ok(word) = self.expect_word("a word") {
+            words.push(word);
+        }
+        return words;
+    }
+
+    fn parse_variant(&mut self) -> Result<Variant, Diagnostic> {
+        let docs = self.take_docs();
+        let annotations = self.take_annotations();
+        let name = self.expect_word("a constant name")?;
+        let mut value = None;
+        let mut span = name.span.clone();
+
+        let mut payload = None;
+
+        match self.peek() {
+            Some(LexemeKind::Symbol('=')) => {
+                self.pos += 1;
+                let literal = self.parse_literal()?;
+                span = span.to(&literal.1);
+                value = Some(literal);
+            }
+            Some(LexemeKind::Symbol('(')) => {
+                self.pos += 1;
+                payload = Some(VariantPayload::Struct(
+                    self.parse_qualified_name("a struct name")?));
+                span = span.to(&self.expect_symbol(')')?);
+            }
+            Some(LexemeKind::Symbol('{')) => {
+                self.pos += 1;
+                let mut fields = vec![];
+
+                while self.peek() != Some(&LexemeKind::Symbol('}')) {
+                    self.parse_inline_annotations()?;
+                    let annotations = self.take_annotations();
+                    let mut field = self.parse_field_body()?;
+                    field.annotations = annotations;
+                    fields.push(field);
+
+                    if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                    self.pos += 1;
+                }
+                span = span.to(&self.expect_symbol('}')?);
+                payload = Some(VariantPayload::Fields(fields));
+            }
+            _ => ()
+        }
+
+        if self.peek() != Some(&LexemeKind::Newline) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::InvalidEnumVariant,
+                Some(name.span.to(&self.line_span()))));
+        }
+        self.expect_newline()?;
+
+        Ok(Variant { name, value, payload, docs, annotations, span })
+    }
+
+    fn parse_literal(&mut self) -> Result<(Literal, SourceSpan), Diagnostic> {
+        let start = self.lexemes.get(self.pos).map(|l| l.span.clone());
+        let negative = self.peek() == Some(&LexemeKind::Symbol('-'));
+
+        if negative { self.pos += 1; }
+
+        let literal = match self.peek().cloned() {
+            Some(LexemeKind::Number(number)) => {
+                let number = if negative { format!("-{}", number) } 
+                    else { number };
+                let parsed = if number.contains(['.', 'e', 'E']) {
+                    number.parse::<f64>().ok().map(Literal::Float)
+                } else {
+                    number.parse::<i128>().ok().map(Literal::Integer)
+                };
+
+                parsed.ok_or(Diagnostic::error(
+                    DiagnosticKind::InvalidLiteral(number),
+                    self.lexemes.get(self.pos).map(|l| l.span.clone())))?
+            }
+            Some(LexemeKind::Text(text)) if !negative => Literal::Text(text),
+            Some(LexemeKind::Word(word)) if !negative && 
+                (word == "true" || word == "false") => 
+                Literal::Bool(word == "true"),
+            _ => return Err(self.unexpected("a value"))
+        };
+        let end = self.advance().span.clone();
+
+        Ok((literal, start.unwrap_or(end.clone()).to(&end)))
+    }
+
+    // Enum values must either be all integers, or all strings. Values also
+    // have to be unique, as they identify constants once serialized.
+    fn check_enum_values(&mut self, variants: &[Variant]) {
+        // Constants of tagged unions are identified by their name.
+        if variants.iter().any(|variant| variant.payload.is_some()) {
+            for (_, span) in variants.iter()
+                .filter_map(|variant| variant.value.as_ref()) {
+                self.diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::ValuedTaggedUnion, Some(span.clone())));
+            }
+            return;
+        }
+
+        // The first value sets the kind of the enum, values of the other kind
+        // being reported.
+        let text_enum = variants.iter()
+            .find_map(|variant| match &variant.value {
+                Some((Literal::Integer(_), _)) => Some(false),
+                Some((Literal::Text(_), _)) => Some(true),
+                _ => None
+            })
+            .unwrap_or(false);
+        let mut next_value = 0;
+        let mut known_values: Vec<Literal> = vec![];
+
+        for variant in variants {
+            let value = match &variant.value {
+                Some((literal @ Literal::Integer(integer), span)) => {
+                    if text_enum {
+                        self.diagnostics.push(Diagnostic::error(
+                            DiagnosticKind::MixedEnumValues,
+                            Some(span.clone())));
+                    }
+                    next_value = integer + 1;
+                    literal.clone()
+                }
+                Some((literal @ Literal::Text(_), span)) => {
+                    if !text_enum {
+                        self.diagnostics.push(Diagnostic::error(
+                            DiagnosticKind::MixedEnumValues,
+                            Some(span.clone())));
+                    }
+                    literal.clone()
+                }
+                Some((_, span)) => {
+                    self.diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::InvalidEnumValue, Some(span.clone())));
+                    continue;
+                }
+                None if text_enum => {
+                    self.diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::MissingEnumValue, 
+                        Some(variant.span.clone())));
+                    continue;
+                }
+                None => {
+                    next_value += 1;
+                    Literal::Integer(next_value - 1)
+                }
+            };
+
+            // Integer enums are generated as 64-bit integers.
+            if let Literal::Integer(integer) = value {
+                if i64::try_from(integer).is_err() {
+                    self.diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::EnumValueOutOfRange(
+                            integer.to_string()),
+                        Some(variant.value.as_ref()
+                            .map_or(&variant.span, |(_, span)| span)
+                            .clone())));
+                    continue;
+                }
+            }
+
+            if known_values.contains(&value) {
+                self.diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::DuplicateEnumValue(
+                        variant.name.name.clone()),
+                    Some(variant.span.clone())));
+            }
+            known_values.push(value);
+        }
+    }
+
+    // Parses `[keyword] [name] NEWLINE`, returning the name, the type
+    // parameters, the extended structs and the span of the whole header. Only
+    // structs can declare type parameters, written as `<[name], [name]>` 
+    // after their name, and extend other structs, written as 
+    // `extends [name], [name]` after them.
+    fn parse_declaration_header(&mut self, token_type: TokenType)
+        -> Result<(Ident, Vec<Ident>, Vec<Ident>, SourceSpan), Diagnostic> {
+        let keyword = self.advance().span.clone();
+
+        if self.peek() == Some(&LexemeKind::Newline) {
+            return Err(Diagnostic::error(
+                DiagnosticKind::MissingDeclarationName(token_type),
+                Some(keyword)));
+        }
+
+        let name = self.expect_word("a name")?;
+        let mut span = keyword.to(&name.span);
+        let mut type_parameters = vec![];
+        let mut bases = vec![];
+
+        if token_type == TokenType::Structure && 
+            self.peek() == Some(&LexemeKind::Symbol('<')) {
+            self.pos += 1;
+
+            loop {
+                type_parameters.push(self.expect_word("a type parameter")?);
+
+                if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                self.pos += 1;
+            }
+            span = span.to(&self.expect_symbol('>')?);
+        }
+
+        if token_type == TokenType::Structure && 
+            self.peek() == Some(&LexemeKind::Word("extends".into())) {
+            self.pos += 1;
+
+            loop {
+                let base = self.parse_qualified_name("a struct")?;
+                span = span.to(&base.span);
+                bases.push(base);
+
+                if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                self.pos += 1;
+            }
+        }
+        self.expect_newline()?;
+
+        Ok((name, type_parameters, bases, span))
+    }
+
+    // Parses the lines following a struct/enum header with `parse_member`,
+    // until another declaration starts.
+    fn parse_members<F>(&mut self, mut parse_member: F)
+        where F: FnMut(&mut Self) -> Result<(), Diagnostic> {
+        loop {
+            self.skip_newlines();
+
+            if self.at_eof() || self.peek_keyword().is_some() { break; }
+            if let Err(diagnostic) = parse_member(self) {
+                self.recover(diagnostic);
+            }
+        }
+    }
+
+    // Reports `diagnostic` and moves to the start of the next line.
+    fn recover(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+        self.pending_docs.clear();
+        self.pending_annotations.clear();
+
+        while let Some(lexeme) = self.lexemes.get(self.pos) {
+            self.pos += 1;
+            if lexeme.kind == LexemeKind::Newline { break; }
+        }
+    }
+
+    fn expect_word(&mut self, expected: &str) -> Result<Ident, Diagnostic> {
+        match self.peek() {
+            Some(LexemeKind::Word(word)) => {
+                let name = word.clone();
+                let span = self.advance().span.clone();
+                Ok(Ident { name, span })
+            }
+            _ => Err(self.unexpected(expected))
+        }
+    }
+
+    // Consumes a string, returning its content and its span.
+    fn expect_text(&mut self, expected: &str) 
+        -> Result<(String, SourceSpan), Diagnostic> {
+        match self.peek() {
+            Some(LexemeKind::Text(text)) => {
+                let text = text.clone();
+                Ok((text, self.advance().span.clone()))
+            }
+            _ => Err(self.unexpected(expected))
+        }
+    }
+
+    // Consumes the `symbol` character, returning its span.
+    fn expect_symbol(&mut self, symbol: char)
+        -> Result<SourceSpan, Diagnostic> {
+        if self.peek() == Some(&LexemeKind::Symbol(symbol)) {
+            return Ok(self.advance().span.clone());
+        }
+        Err(self.unexpected(&format!("`{}`", symbol)))
+    }
+
+    fn expect_newline(&mut self) -> Result<(), Diagnostic> {
+        match self.peek() {
+            Some(LexemeKind::Newline) | None => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(self.unexpected("end of line"))
+        }
+    }
+
+    // Builds an error about the current lexeme not being what's expected.
+    fn unexpected(&self, expected: &str) -> Diagnostic {
+        let (found, span) = match self.lexemes.get(self.pos) {
+            Some(lexeme) => (lexeme.kind.describe(), Some(lexeme.span.clone())),
+            None => ("end of file".into(), None)
+        };
+
+        Diagnostic::error(
+            DiagnosticKind::UnexpectedToken {
+                expected: expected.to_string(), found },
+            span)
+    }
+
+    // Span going from the current lexeme to the end of its line.
+    fn line_span(&self) -> SourceSpan {
+        let start = self.lexemes[self.pos].span.clone();
+        let end = self.lexemes[self.pos..].iter()
+            .take_while(|lexeme| lexeme.kind != LexemeKind::Newline)
+            .last()
+            .map(|lexeme| lexeme.span.clone())
+            .unwrap_or(start.clone());
+
+        start.to(&end)
+    }
+
+    // Skips blank lines, collecting the doc comments and the annotations
+    // found on the way.
+    fn skip_newlines(&mut self) {
+        loop {
+            match self.peek() {
+                Some(LexemeKind::Newline) => (),
+                Some(LexemeKind::DocComment(text)) => 
+                    self.pending_docs.push(text.clone()),
+                Some(LexemeKind::Symbol('@')) => {
+                    if let Err(diagnostic) = self.parse_annotations() {
+                        self.recover(diagnostic);
+                    }
+                    continue;
+                }
+                _ => break
+            }
+            self.pos += 1;
+        }
+    }
+
+    // Parses a line of `@[name]` or `@[name]([argument], [argument])`
+    // annotations, kept until the node below them is parsed.
+    fn parse_annotations(&mut self) -> Result<(), Diagnostic> {
+        self.parse_inline_annotations()?;
+        self.expect_newline()
+    }
+
+    // Parses the annotations preceding a node on the same line, such as a
+    // field of an inline variant.
+    fn parse_inline_annotations(&mut self) -> Result<(), Diagnostic> {
+        while self.peek() == Some(&LexemeKind::Symbol('@')) {
+            let at = self.advance().span.clone();
+            let name = self.expect_word("an annotation name")?;
+            let mut span = at.to(&name.span);
+            let mut arguments = vec![];
+
+            if self.peek() == Some(&LexemeKind::Symbol('(')) {
+                self.pos += 1;
+
+                while self.peek() != Some(&LexemeKind::Symbol(')')) {
+                    arguments.push(self.parse_annotation_argument()?);
+
+                    if self.peek() != Some(&LexemeKind::Symbol(',')) { break; }
+                    self.pos += 1;
+                }
+                span = span.to(&self.expect_symbol(')')?);
+            }
+            self.pending_annotations.push(
+                Annotation { name, arguments, span });
+        }
+        Ok(())
+    }
+
+    // Parses `[literal]` or `[name]: [literal]`.
+    fn parse_annotation_argument(&mut self) 
+        -> Result<AnnotationArgument, Diagnostic> {
+        let is_named = matches!(self.peek(), Some(LexemeKind::Word(_))) &&
+            self.lexemes.get(self.pos + 1)
+                .is_some_and(|lexeme| lexeme.kind == LexemeKind::Symbol(':'));
+        let name = if is_named {
+            let name = self.expect_word("an argument name")?;
+            self.pos += 1;
+            Some(name)
+        } else { None };
+
+        Ok(AnnotationArgument { name, value: self.parse_literal()? })
+    }
+
+    fn take_docs(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending_docs)
+    }
+
+    fn take_annotations(&mut self) -> Vec<Annotation> {
+        std::mem::take(&mut self.pending_annotations)
+    }
+
+    fn peek(&self) -> Option<&LexemeKind> {
+        self.lexemes.get(self.pos).map(|lexeme| &lexeme.kind)
+    }
+
+    // Returns the current lexeme if it's a declaration keyword.
+    fn peek_keyword(&self) -> Option<&'static str> {
+        match self.peek() {
+            Some(LexemeKind::Word(word)) => DECLARATION_KEYWORDS.iter()
+                .find(|keyword| *keyword == word)
+                .copied(),
+            _ => None
+        }
+    }
+
+    fn advance(&mut self) -> &Lexeme {
+        self.pos += 1;
+        &self.lexemes[self.pos - 1]
+    }
+
+    fn at_eof(&self) -> bool {
+        self.pos >= self.lexemes.len()
+    }
+}
+
+// Reads the parameters of a type out of their keywords.
+fn parameters_from_words(words: Vec<Ident>)
+    -> Result<Vec<(TokenParameter, SourceSpan)>, Diagnostic> {
+    words.into_iter()
+        .map(|word| match TokenParameter::from_keyword(&word.name) {
+            Some(parameter) => Ok((parameter, word.span)),
+            None => Err(Diagnostic::error(
+                DiagnosticKind::UnknownParameter(word.name),
+                Some(word.span)))
+        })
+        .collect()
+}
+
+// Writes a type along with its parameters, to be used in diagnostics.
+fn type_description(type_ref: &TypeRef) -> String {
+    type_ref.parameters.iter()
+        .fold(type_ref.name.name.clone(), |name, (parameter, _)| 
+              format!("{} {}", name, parameter.keyword()))
+}
+
+// Constraints that can be applied to a field of type `field_type`: bounds to
+// numbers, lengths to strings and collections and patterns to strings.
+// Pointers and references are left unchecked.
+fn applicable_constraints(field_type: &TypeRef) -> &'static [&'static str] {
+    let has_parameter = |expected: TokenParameter| field_type.parameters.iter()
+        .any(|(parameter, _)| *parameter == expected);
+
+    if has_parameter(TokenParameter::Pointer) || 
+        has_parameter(TokenParameter::Reference) {
+        return &[];
+    }
+
+    match TokenType::from_keyword(&field_type.name.name) {
+        _ if has_parameter(TokenParameter::Vector) => &["length", "non_empty"],
+        TokenType::Map | TokenType::Vector | TokenType::Set => 
+            &["length", "non_empty"],
+        TokenType::String => &["length", "pattern", "non_empty"],
+        TokenType::IntU8 | TokenType::IntU16 | TokenType::IntU32 
+            | TokenType::IntU64 | TokenType::IntI8 | TokenType::IntI16 
+            | TokenType::IntI32 | TokenType::IntI64 | TokenType::Float32 
+            | TokenType::Float64 => &["min", "max"],
+        _ => &[]
+    }
+}
+
+fn declaration_token_type(keyword: &str) -> TokenType {
+    match keyword {
+        "module" => TokenType::Module,
+        "struct" => TokenType::Structure,
+        "alias" => TokenType::Alias,
+        "newtype" => TokenType::NewType,
+        "const" => TokenType::Constant,
+        "extern" => TokenType::Extern,
+        "fields" => TokenType::FieldGroup,
+        _ => TokenType::Enumeration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::lexer::tokenize;
+
+    fn parse(source: &str) -> (Vec<Module>, Vec<DiagnosticKind>) {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(source, "test.tc", &mut diagnostics);
+        let modules = Parser::new(&lexemes, &mut diagnostics).parse_file();
+
+        return (modules, diagnostics.entries.into_iter()
+            .map(|diagnostic| diagnostic.kind).collect());
+    }
+
+    // Diagnostics of `body` once placed in a module.
+    fn diagnostics(body: &str) -> Vec<DiagnosticKind> {
+        parse(&format!("module M\n{}\n", body)).1
+    }
+
+    fn fields(module: &Module) -> Vec<&Field> {
+        match &module.items[0] {
+            Item::Struct(declaration) => declaration.members.iter()
+                .filter_map(|member| match member {
+                    Member::Field(field) => Some(field.as_ref()),
+                    Member::Include(_) => None
+                })
+                .collect(),
+            item => panic!("expected a struct, found {:?}", item)
+        }
+    }
+
+    #[test]
+    fn crlf_and_tabs() {
+        let (modules, diagnostics) = parse(
+            "module M\r\n\r\nstruct A\r\n\tstring\tName\r\n\t\tint_u8 Age\r\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let names = fields(&modules[0]).iter()
+            .map(|field| field.name.name.clone())
+            .collect::<Vec<String>>();
+        assert_eq!(names, ["Name", "Age"]);
+    }
+
+    #[test]
+    fn comments_and_separators() {
+        let (modules, diagnostics) = parse(
+            "module M : comment\n: comment\n:: Documented.\nstruct A\n    \
+             @column(name: \"id\") : comment\n    string Id : comment\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let declaration = match &modules[0].items[0] {
+            Item::Struct(declaration) => declaration,
+            item => panic!("expected a struct, found {:?}", item)
+        };
+        assert_eq!(declaration.docs, ["Documented."]);
+
+        let annotation = &fields(&modules[0])[0].annotations[0];
+        assert_eq!(annotation.arguments[0].name.as_ref().unwrap().name, "name");
+        assert_eq!(annotation.arguments[0].value.0, Literal::Text("id".into()));
+    }
+
+    #[test]
+    fn annotations() {
+        let (modules, diagnostics) = parse(
+            "@internal\nmodule M\n@table(name: \"users\", 3) @audited\n\
+             struct A\n    @rename(json : \"id\")\n    string Id\n\
+             enum E\n    @deprecated(\"gone\")\n    \
+             Started { @rename(\"at\") string StartedAt }\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let names = |annotations: &[Annotation]| annotations.iter()
+            .map(|annotation| annotation.name.name.clone())
+            .collect::<Vec<String>>();
+        assert_eq!(names(&modules[0].annotations), ["internal"]);
+
+        let (declaration, variant) = match &modules[0].items[..] {
+            [Item::Struct(declaration), Item::Enum(enumeration)] => 
+                (declaration, &enumeration.variants[0]),
+            items => panic!("expected a struct and an enum, found {:?}", items)
+        };
+        assert_eq!(names(&declaration.annotations), ["table", "audited"]);
+        let arguments = &declaration.annotations[0].arguments;
+        assert_eq!(arguments.iter()
+            .map(|argument| (argument.name.as_ref()
+                .map(|name| name.name.clone()), argument.value.0.clone()))
+            .collect::<Vec<(Option<String>, Literal)>>(), [
+                (Some("name".into()), Literal::Text("users".into())),
+                (None, Literal::Integer(3))]);
+
+        let field = fields(&modules[0])[0];
+        assert_eq!(names(&field.annotations), ["rename"]);
+        assert_eq!(field.annotations[0].arguments[0].name.as_ref()
+            .map(|name| name.name.as_str()), Some("json"));
+
+        assert_eq!(names(&variant.annotations), ["deprecated"]);
+        match &variant.payload {
+            Some(VariantPayload::Fields(fields)) => 
+                assert_eq!(names(&fields[0].annotations), ["rename"]),
+            payload => panic!("expected inline fields, found {:?}", payload)
+        }
+    }
+
+    #[test]
+    fn exponent_numbers() {
+        let (modules, diagnostics) = parse(
+            "module M\nconst float_64 Big = 1e5\n\
+             const float_32 Small = -2.5e-3\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let values = modules[0].items.iter()
+            .map(|item| match item {
+                Item::Const(constant) => constant.value.0.clone(),
+                item => panic!("expected a constant, found {:?}", item)
+            })
+            .collect::<Vec<Literal>>();
+        assert_eq!(values, [Literal::Float(1e5), Literal::Float(-2.5e-3)]);
+    }
+
+    #[test]
+    fn recovers_on_the_next_line() {
+        let (modules, diagnostics) = parse(
+            "module M\nstruct A\n    string weird Name\n    int_u8 Age\n");
+
+        assert_eq!(diagnostics, 
+                   [DiagnosticKind::UnknownParameter("weird".into())]);
+        assert_eq!(fields(&modules[0]).len(), 1);
+    }
+
+    #[test]
+    fn declaration_errors() {
+        assert_eq!(parse("struct A\n").1, 
+                   [DiagnosticKind::DeclarationOutsideModule(
+                       TokenType::Structure)]);
+        assert_eq!(parse("string Name\n").1, 
+                   [DiagnosticKind::FieldOutsideDeclaration]);
+        assert_eq!(diagnostics("string Name"), 
+                   [DiagnosticKind::FieldOutsideDeclaration]);
+        assert_eq!(parse("module\n").1, 
+                   [DiagnosticKind::MissingDeclarationName(
+                       TokenType::Module)]);
+        assert_eq!(diagnostics("struct"), 
+                   [DiagnosticKind::MissingDeclarationName(
+                       TokenType::Structure)]);
+        assert_eq!(diagnostics("alias"), 
+                   [DiagnosticKind::MissingDeclarationName(
+                       TokenType::Alias)]);
+        assert_eq!(diagnostics("const"), 
+                   [DiagnosticKind::MissingDeclarationName(
+                       TokenType::Constant)]);
+        assert_eq!(diagnostics("extern Money\n    py \"Decimal\""), 
+                   [DiagnosticKind::UnknownExternLanguage("py".into())]);
+    }
+
+    #[test]
+    fn field_errors() {
+        assert_eq!(diagnostics("struct A\n    string"), 
+                   [DiagnosticKind::MissingFieldName]);
+        assert_eq!(diagnostics("struct A\n    string weird Name"), 
+                   [DiagnosticKind::UnknownParameter("weird".into())]);
+        assert_eq!(diagnostics("struct A\n    string Name ="), 
+                   [DiagnosticKind::UnexpectedToken { 
+                       expected: "a value".into(), 
+                       found: "end of line".into() }]);
+        assert_eq!(diagnostics("struct A\n    tuple<string> Pair"), 
+                   [DiagnosticKind::UnexpectedToken { 
+                       expected: "a type".into(), found: "`tuple`".into() }]);
+        assert_eq!(diagnostics("struct A\n    map<string> Names"), 
+                   [DiagnosticKind::TypeArgumentCount { 
+                       type_name: "map".into(), expected: 2, found: 1 }]);
+        assert_eq!(diagnostics("struct A\n    array<string, 0> Names"), 
+                   [DiagnosticKind::InvalidArrayLength("0".into())]);
+        assert_eq!(diagnostics("struct A\n    string floated Name"), 
+                   [DiagnosticKind::FloatedOnNonInteger("string".into())]);
+        assert_eq!(diagnostics("struct A\n    int_u8 floated Ratio"), 
+                   [DiagnosticKind::DeprecatedFloated]);
+    }
+
+    #[test]
+    fn value_errors() {
+        assert_eq!(diagnostics("const int_u8 Max = 300"), 
+                   [DiagnosticKind::MismatchedValue { 
+                       value: "300".into(), type_name: "int_u8".into() }]);
+        assert_eq!(diagnostics("const int_u128 Max = 1"), 
+                   [DiagnosticKind::NonPrimitiveConst("int_u128".into())]);
+        let digits = "9".repeat(40);
+        assert_eq!(diagnostics(&format!("const int_u64 Max = {}", digits)), 
+                   [DiagnosticKind::InvalidLiteral(digits)]);
+        assert_eq!(diagnostics("struct A\n    string vec Names = \"a\""), 
+                   [DiagnosticKind::UnsupportedDefault("string vec".into())]);
+        assert_eq!(diagnostics("struct A\n    uuid Id = \"a\""), 
+                   [DiagnosticKind::UnsupportedDefault("uuid".into())]);
+    }
+
+    #[test]
+    fn constraint_errors() {
+        assert_eq!(diagnostics("struct A\n    string Name [unique]"), 
+                   [DiagnosticKind::UnknownConstraint("unique".into())]);
+        assert_eq!(diagnostics("struct A\n    bool Flag [min(0)]"), 
+                   [DiagnosticKind::InapplicableConstraint { 
+                       constraint: "min".into(), type_name: "bool".into() }]);
+        assert_eq!(diagnostics("struct A\n    string Name [pattern(1)]"), 
+                   [DiagnosticKind::InvalidConstraintArguments { 
+                       constraint: "pattern".into(), 
+                       expected: "a single string".into() }]);
+        assert_eq!(diagnostics("struct A\n    string Name [pattern(\"(a\")]"), 
+                   [DiagnosticKind::InvalidPattern("unclosed group".into())]);
+        assert_eq!(diagnostics("struct A\n    int_u8 Age [max(300)]"), 
+                   [DiagnosticKind::MismatchedValue { 
+                       value: "300".into(), type_name: "int_u8".into() }]);
+    }
+
+    #[test]
+    fn mixed_enum_values_point_at_the_other_kind() {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(
+            "module M\nenum E\n    A = 1\n    B\n    C = \"c\"\n    D = 4\n", 
+            "test.tc", &mut diagnostics);
+        Parser::new(&lexemes, &mut diagnostics).parse_file();
+
+        assert_eq!(diagnostics.entries.iter()
+            .map(|diagnostic| (diagnostic.kind.clone(), 
+                               diagnostic.span.as_ref().unwrap().line))
+            .collect::<Vec<(DiagnosticKind, usize)>>(), 
+            [(DiagnosticKind::MixedEnumValues, 5)]);
+    }
+
+    #[test]
+    fn enum_errors() {
+        assert_eq!(diagnostics("enum E\n    A B"), 
+                   [DiagnosticKind::InvalidEnumVariant]);
+        assert_eq!(diagnostics("enum E\n    A = true"), 
+                   [DiagnosticKind::InvalidEnumValue]);
+        assert_eq!(diagnostics("enum E\n    A = \"a\"\n    B = 1"), 
+                   [DiagnosticKind::MixedEnumValues]);
+        assert_eq!(diagnostics("enum E\n    A = \"a\"\n    B"), 
+                   [DiagnosticKind::MissingEnumValue]);
+        assert_eq!(diagnostics("enum E\n    A = 1\n    B = 1"), 
+                   [DiagnosticKind::DuplicateEnumValue("B".into())]);
+        assert_eq!(diagnostics("enum E\n    A = 1\n    B { string X }"), 
+                   [DiagnosticKind::ValuedTaggedUnion]);
+    }
+
+    #[test]
+    fn enum_values_fit_in_64_bits() {
+        assert!(diagnostics(
+            "enum E\n    A = -9223372036854775808\n    \
+             B = 9223372036854775807").is_empty());
+
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(
+            "module M\nenum E\n    A = 99999999999999999999\n    \
+             B = 9223372036854775807\n    C\n", 
+            "test.tc", &mut diagnostics);
+        Parser::new(&lexemes, &mut diagnostics).parse_file();
+
+        // Implicit values following the last one are checked as well.
+        assert_eq!(diagnostics.entries.iter()
+            .map(|diagnostic| (diagnostic.kind.clone(), 
+                               diagnostic.span.as_ref().unwrap().line,
+                               diagnostic.span.as_ref().unwrap().column))
+            .collect::<Vec<(DiagnosticKind, usize, usize)>>(), [
+                (DiagnosticKind::EnumValueOutOfRange(
+                    "99999999999999999999".into()), 3, 9),
+                (DiagnosticKind::EnumValueOutOfRange(
+                    "9223372036854775808".into()), 5, 5)]);
+    }
+}
@@ -1,43 +1,74 @@
-use super::tokenizer::{TokenType, TokenSet};
+use std::collections::HashMap;
+
+use heck::{
+    ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase};
+
+use super::ast::{Annotation, AnnotationArgument, Literal};
+use super::diagnostics::{Diagnostic, DiagnosticKind, Diagnostics, SourceSpan};
+use super::tokenizer::{TokenType, TokenSet, TokenParameter};
 use crate::debug;
 
 #[derive(Clone, Debug)]
 pub struct ItemDeclarationDescriptor {
-    pub module_name: String,
+    // Names of the module the declaration is in and of the modules it's
+    // nested in, from the outermost one.
+    pub module_path: Vec<String>,
     pub declaration_name: String,
-    pub declaration_type: TokenType
+    pub declaration_type: TokenType,
+    // Number of type parameters of the declaration.
+    pub arity: usize,
+    // Type an alias or a newtype stands for.
+    pub aliased_type: Option<TokenSet>
 }
 
 // Contains a flatten tree of items declarations.
 #[derive(Clone, Debug)]
 pub struct ReusableDeclarations {
     pub structs: Vec<ItemDeclarationDescriptor>,
-    pub enums: Vec<ItemDeclarationDescriptor>
+    pub enums: Vec<ItemDeclarationDescriptor>,
+    // Aliases and newtypes.
+    pub aliases: Vec<ItemDeclarationDescriptor>,
+    pub externs: Vec<ItemDeclarationDescriptor>,
+    pub field_groups: Vec<ItemDeclarationDescriptor>
 }
 
 impl ReusableDeclarations {
     // Build the flatten tree from modules token sets.
     pub fn from_token_sets_vec(source: Vec<TokenSet>) -> Self {
-        let mut output = ReusableDeclarations { structs: vec![], enums: vec![] };
+        let mut output = ReusableDeclarations { 
+            structs: vec![], enums: vec![], aliases: vec![], externs: vec![],
+            field_groups: vec![] };
 
         source.iter().for_each(|token| {
             // Doesn't process the token if it's not a module.
             if token.token_type == TokenType::Module {
-                for child in token.childs.clone() {
+                for (module_path, child) in token.declarations() {
                     // Builds the declaration descriptor before further checks.
+                    let is_alias = child.token_type == TokenType::Alias ||
+                        child.token_type == TokenType::NewType;
                     let declaration_descriptor = ItemDeclarationDescriptor {
-                        module_name: token.token_name.clone(),
-                        declaration_name: child.token_name,
-                        declaration_type: child.token_type
+                        module_path,
+                        declaration_name: child.token_name.clone(),
+                        declaration_type: child.token_type.clone(),
+                        arity: child.type_parameters.len(),
+                        aliased_type: if is_alias { 
+                            child.childs.first().cloned() } else { None }
                     };
 
-                    // Checks it for being either a struct or an enum because
-                    // nothing else should be a child of a module.
+                    // Checks it for being a struct, an enum, an alias, an
+                    // extern type or a field group, constants not being 
+                    // types.
                     match declaration_descriptor.declaration_type {
                         TokenType::Structure => { 
                             output.structs.push(declaration_descriptor) },
                         TokenType::Enumeration => {
                             output.enums.push(declaration_descriptor) },
+                        TokenType::Alias | TokenType::NewType => {
+                            output.aliases.push(declaration_descriptor) },
+                        TokenType::Extern => {
+                            output.externs.push(declaration_descriptor) },
+                        TokenType::FieldGroup => {
+                            output.field_groups.push(declaration_descriptor) },
                         _ => ()
                     }
                 }
@@ -49,16 +80,1056 @@ impl ReusableDeclarations {
        return output;
     }
 
-    // Searches a `ItemDeclarationDescriptor` by `declaration_name`.
-    pub fn find_declaration_descriptor_with_declaration_name(
-        &self, declaration_name: String) -> Option<&ItemDeclarationDescriptor> {
-        let struct_search = self.structs.iter()
-            .find(|item| item.declaration_name == declaration_name);
-        let enum_search = self.enums.iter()
-            .find(|item| item.declaration_name == declaration_name);
+    // Finds the declaration a local type named `name` stands for, from the
+    // module at `from_module`. Bare names are first searched in that module,
+    // then in every other one, where they must be unique. Qualified names,
+    // such as `Billing.Status`, are searched from that module and from its
+    // parents, the outermost one being the root.
+    pub fn resolve_declaration(&self, name: &str, from_module: &[String])
+        -> Result<&ItemDeclarationDescriptor, DiagnosticKind> {
+        let mut path = name.split('.').map(String::from).collect::<Vec<_>>();
+        let declaration_name = path.pop().unwrap();
+        let declarations = || self.structs.iter()
+            .chain(self.enums.iter())
+            .chain(self.aliases.iter())
+            .chain(self.externs.iter())
+            .chain(self.field_groups.iter())
+            .filter(|item| item.declaration_name == declaration_name);
+
+        if !path.is_empty() {
+            return (0..=from_module.len()).rev()
+                .find_map(|depth| {
+                    let module_path = [&from_module[..depth], &path].concat();
+                    declarations()
+                        .find(|item| item.module_path == module_path)
+                })
+                .ok_or(DiagnosticKind::UnresolvedLocalType(name.into()));
+        }
+
+        if let Some(declaration) = declarations()
+            .find(|item| item.module_path == from_module) {
+            return Ok(declaration);
+        }
+
+        match declarations().collect::<Vec<_>>().as_slice() {
+            [] => Err(DiagnosticKind::UnresolvedLocalType(name.into())),
+            [declaration] => Ok(declaration),
+            candidates => Err(DiagnosticKind::AmbiguousLocalType {
+                name: name.into(),
+                candidates: candidates.iter()
+                    .map(|item| item.qualified_name()).collect() })
+        }
+    }
+
+    // Resolves every type using the `local` parameter to the declaration it
+    // stands for, its name being replaced with the qualified name of the
+    // declaration. Types that don't point to a declaration within the
+    // TypeCode files are reported, along with tagged union constants that
+    // don't wrap a declared struct and aliases standing for themselves.
+    // Once every type is resolved, field groups are replaced by their fields
+    // and the structs a struct extends are given the fields they bring.
+    pub fn resolve_local_types(
+        &self, source: &mut [TokenSet], diagnostics: &mut Diagnostics) {
+        for module in source.iter_mut() {
+            self.resolve_module(module, &[], diagnostics);
+        }
+
+        let field_groups = source.iter()
+            .flat_map(|module| module.declarations())
+            .filter(|(_, declaration)| 
+                    declaration.token_type == TokenType::FieldGroup)
+            .map(|(module_path, declaration)| (
+                format!("{}.{}", module_path.join("."), declaration.token_name),
+                declaration.clone()))
+            .collect::<HashMap<String, TokenSet>>();
+
+        for (module_path, declaration) in source.iter()
+            .flat_map(|module| module.declarations()) {
+            if declaration.token_type == TokenType::FieldGroup {
+                check_field_group_cycle(declaration, &module_path, 
+                                        &field_groups, diagnostics);
+            }
+        }
+        for module in source.iter_mut() {
+            expand_field_groups(module, &field_groups, diagnostics);
+        }
+        // Names are given once field groups are expanded, their fields
+        // following the rule of the struct including them.
+        for module in source.iter_mut() {
+            resolve_serialized_names(module, diagnostics);
+        }
+
+        // Structs are indexed by their qualified name, as extended structs
+        // are once resolved.
+        let structs = source.iter()
+            .flat_map(|module| module.declarations())
+            .filter(|(_, declaration)| 
+                    declaration.token_type == TokenType::Structure)
+            .map(|(module_path, declaration)| (
+                format!("{}.{}", module_path.join("."), declaration.token_name),
+                declaration.clone()))
+            .collect::<HashMap<String, TokenSet>>();
+
+        for module in source.iter_mut() {
+            self.resolve_module_bases(module, &[], &structs, diagnostics);
+        }
+    }
+
+    fn resolve_module(
+        &self, 
+        module: &mut TokenSet, 
+        parent_path: &[String], 
+        diagnostics: &mut Diagnostics) {
+        let mut module_path = parent_path.to_vec();
+        module_path.push(module.token_name.clone());
+
+        for declaration in module.childs.iter_mut() {
+            if declaration.token_type == TokenType::Module {
+                self.resolve_module(declaration, &module_path, diagnostics);
+                continue;
+            }
+
+            if declaration.token_type == TokenType::Alias {
+                self.check_alias_cycle(declaration, &module_path, diagnostics);
+            }
+
+            for base in declaration.bases.iter_mut() {
+                self.resolve_base(base, &module_path, diagnostics);
+            }
+
+            let is_enum = declaration.token_type == TokenType::Enumeration;
+
+            for child in declaration.childs.iter_mut() {
+                if !is_enum {
+                    self.resolve_local_type(child, &module_path, diagnostics);
+                    continue;
+                }
+
+                child.childs.iter_mut().for_each(|field| 
+                    self.resolve_local_type(field, &module_path, diagnostics));
+
+                if let Some(payload) = &mut child.payload {
+                    let name = payload.custom_token_type.clone().unwrap();
+
+                    match self.resolve_declaration(&name, &module_path) {
+                        Ok(declaration) if declaration.declaration_type == 
+                            TokenType::Structure => {
+                            self.check_arity(payload, declaration, diagnostics);
+                            payload.custom_token_type = 
+                                Some(declaration.qualified_name());
+                        }
+                        Ok(_) | Err(DiagnosticKind::UnresolvedLocalType(_)) => 
+                            diagnostics.push(Diagnostic::error(
+                                DiagnosticKind::PayloadNotStruct(name),
+                                Some(payload.span.clone()))),
+                        Err(kind) => diagnostics.push(
+                            self.resolution_error(kind, &payload.span))
+                    }
+                }
+            }
+        }
+    }
+
+    fn resolve_local_type(
+        &self, 
+        field: &mut TokenSet, 
+        module_path: &[String], 
+        diagnostics: &mut Diagnostics) {
+        // Map keys and set items are checked before their name is 
+        // qualified, as they are resolved from the module they are used in.
+        if field.token_type == TokenType::Map {
+            self.check_map_key(&field.type_arguments[0], module_path, 
+                               DiagnosticKind::InvalidMapKey, diagnostics);
+        }
+        if field.token_type == TokenType::Set {
+            self.check_map_key(&field.type_arguments[0], module_path, 
+                               DiagnosticKind::InvalidSetItem, diagnostics);
+        }
+
+        field.type_arguments.iter_mut().for_each(|argument| 
+            self.resolve_local_type(argument, module_path, diagnostics));
+
+        if !field.parameters.contains(&TokenParameter::LocalType) { return; }
+
+        match &field.custom_token_type {
+            Some(custom_type) => {
+                match self.resolve_declaration(custom_type, module_path) {
+                    // Field groups can only be included, and only them.
+                    Ok(declaration) if (field.token_type == 
+                        TokenType::FieldGroup) != (declaration.declaration_type
+                        == TokenType::FieldGroup) => {
+                        diagnostics.push(
+                            if field.token_type == TokenType::FieldGroup {
+                                Diagnostic::error(
+                                    DiagnosticKind::NotFieldGroup(
+                                        custom_type.clone()),
+                                    Some(field.span.clone()))
+                            } else {
+                                Diagnostic::error(
+                                    DiagnosticKind::FieldGroupAsType(
+                                        custom_type.clone()),
+                                    Some(field.span.clone()))
+                                    .with_note("field groups are pulled into \
+                                                structs with `include`")
+                            });
+                    }
+                    Ok(declaration) => {
+                        self.check_arity(field, declaration, diagnostics);
+                        field.custom_token_type = 
+                            Some(declaration.qualified_name());
+                    }
+                    Err(kind) => diagnostics.push(
+                        self.resolution_error(kind, &field.span))
+                }
+            }
+            None => diagnostics.push(Diagnostic::error(
+                DiagnosticKind::LocalOnBuiltinType(
+                    field.token_type.clone()),
+                Some(field.span.clone())))
+        }
+    }
+
+    // Resolves a struct extended by another one, which can't be generic as
+    // its fields are added to the ones of the other struct.
+    fn resolve_base(
+        &self, 
+        base: &mut TokenSet, 
+        module_path: &[String], 
+        diagnostics: &mut Diagnostics) {
+        let name = base.custom_token_type.clone().unwrap();
+
+        match self.resolve_declaration(&name, module_path) {
+            Ok(declaration) if declaration.declaration_type != 
+                TokenType::Structure => 
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::BaseNotStruct(name), 
+                    Some(base.span.clone()))),
+            Ok(declaration) if declaration.arity > 0 => 
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::GenericBase(name), 
+                    Some(base.span.clone()))),
+            Ok(declaration) => 
+                base.custom_token_type = Some(declaration.qualified_name()),
+            Err(kind) => diagnostics.push(
+                self.resolution_error(kind, &base.span))
+        }
+    }
+
+    fn resolve_module_bases(
+        &self, 
+        module: &mut TokenSet, 
+        parent_path: &[String], 
+        structs: &HashMap<String, TokenSet>,
+        diagnostics: &mut Diagnostics) {
+        let mut module_path = parent_path.to_vec();
+        module_path.push(module.token_name.clone());
+
+        for declaration in module.childs.iter_mut() {
+            if declaration.token_type == TokenType::Module {
+                self.resolve_module_bases(
+                    declaration, &module_path, structs, diagnostics);
+                continue;
+            }
+            if declaration.bases.is_empty() { continue; }
+
+            let qualified_name = format!("{}.{}", 
+                module_path.join("."), declaration.token_name);
+            // Names of the fields along with the struct they come from.
+            let mut field_origins: Vec<(String, String)> = vec![];
+
+            for base in declaration.bases.iter_mut() {
+                let base_name = base.custom_token_type.clone().unwrap();
+                let mut visited = vec![qualified_name.clone()];
+
+                match inherited_fields(&base_name, structs, &mut visited) {
+                    Some(fields) => base.childs = fields,
+                    None => {
+                        diagnostics.push(Diagnostic::error(
+                            DiagnosticKind::RecursiveBase(
+                                declaration.token_name.clone()),
+                            Some(base.span.clone())));
+                        continue;
+                    }
+                }
+
+                for field in base.childs.iter() {
+                    check_field_origin(field, &base_name, &base.span, 
+                                       &mut field_origins, diagnostics);
+                }
+            }
+
+            for field in declaration.childs.iter() {
+                check_field_origin(field, &declaration.token_name, &field.span,
+                                   &mut field_origins, diagnostics);
+            }
+        }
+    }
+
+    // Builds the error reported when a local type can't be resolved.
+    fn resolution_error(&self, kind: DiagnosticKind, span: &SourceSpan) 
+        -> Diagnostic {
+        let note = match &kind {
+            DiagnosticKind::AmbiguousLocalType { candidates, .. } => Some(
+                format!("qualify it with its module: {}", candidates.iter()
+                    .map(|candidate| format!("`{}`", candidate))
+                    .collect::<Vec<String>>().join(", "))),
+            _ => None
+        };
+        let diagnostic = Diagnostic::error(kind, Some(span.clone()));
+
+        match note {
+            Some(note) => diagnostic.with_note(note),
+            None => diagnostic
+        }
+    }
+
+    // Reports generic declarations used with the wrong number of type
+    // arguments.
+    fn check_arity(
+        &self, 
+        token: &TokenSet, 
+        declaration: &ItemDeclarationDescriptor, 
+        diagnostics: &mut Diagnostics) {
+        if token.type_arguments.len() != declaration.arity {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::TypeArgumentCount {
+                    type_name: declaration.declaration_name.clone(),
+                    expected: declaration.arity,
+                    found: token.type_arguments.len() },
+                Some(token.span.clone())));
+        }
+    }
+
+    // Reports aliases standing for themselves, directly or through other
+    // aliases, as no language is able to resolve them. Newtypes are distinct
+    // types and can thus be recursive.
+    fn check_alias_cycle(
+        &self, 
+        alias: &TokenSet, 
+        module_path: &[String], 
+        diagnostics: &mut Diagnostics) {
+        let qualified_name = format!("{}.{}", 
+                                     module_path.join("."), alias.token_name);
+        let mut visited: Vec<String> = vec![];
+        // Types are resolved from the module they are used in.
+        let mut pending: Vec<(TokenSet, Vec<String>)> = alias.childs.iter()
+            .map(|child| (child.clone(), module_path.to_vec())).collect();
+
+        while let Some((token, from_module)) = pending.pop() {
+            pending.extend(token.type_arguments.iter()
+                .map(|argument| (argument.clone(), from_module.clone())));
+
+            if !token.parameters.contains(&TokenParameter::LocalType) { 
+                continue; 
+            }
+
+            let name = token.custom_token_type.unwrap_or_default();
+            let declaration = match self.resolve_declaration(
+                &name, &from_module) {
+                Ok(declaration) => declaration,
+                Err(_) => continue
+            };
+
+            if declaration.qualified_name() == qualified_name {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::RecursiveAlias(alias.token_name.clone()),
+                    Some(alias.span.clone())));
+                return;
+            }
+            if visited.contains(&declaration.qualified_name()) { continue; }
+            visited.push(declaration.qualified_name());
+
+            if declaration.declaration_type == TokenType::Alias {
+                pending.extend(declaration.aliased_type.iter().map(|aliased| 
+                    (aliased.clone(), declaration.module_path.clone())));
+            }
+        }
+    }
+
+    // Map keys have to be strings, integers or enums declared within the
+    // TypeCode files, as every generator is able to use them as keys. Aliases
+    // of such types are valid keys as well, along with newtypes of strings
+    // and integers.
+    fn is_map_key(
+        &self, 
+        key: &TokenSet, 
+        from_module: &[String], 
+        visited: &mut Vec<String>) -> bool {
+        let is_local = key.parameters.contains(&TokenParameter::LocalType);
+
+        if key.parameters.iter()
+            .any(|parameter| *parameter != TokenParameter::LocalType) {
+            return false;
+        }
+
+        match key.token_type {
+            TokenType::String | TokenType::IntU8 | TokenType::IntU16 
+                | TokenType::IntU32 | TokenType::IntU64 | TokenType::IntI8 
+                | TokenType::IntI16 | TokenType::IntI32 
+                | TokenType::IntI64 => true,
+            // Unresolved local types and recursive aliases are already 
+            // reported.
+            TokenType::Custom if !is_local => false,
+            TokenType::Custom => {
+                let name = key.custom_token_type.clone().unwrap();
+                let item = match self.resolve_declaration(&name, from_module) {
+                    Ok(item) => item,
+                    Err(_) => return true
+                };
+
+                if visited.contains(&item.qualified_name()) { return true; }
+                visited.push(item.qualified_name());
+
+                match (&item.declaration_type, &item.aliased_type) {
+                    (TokenType::Enumeration, _) => true,
+                    (TokenType::Alias, Some(aliased)) => 
+                        self.is_map_key(aliased, &item.module_path, visited),
+                    (TokenType::NewType, Some(aliased)) => 
+                        aliased.token_type != TokenType::Custom &&
+                        self.is_map_key(aliased, &item.module_path, visited),
+                    _ => false
+                }
+            }
+            _ => false
+        }
+    }
+
+    // Checks a map key or a set item, as sets are maps in some languages,
+    // `invalid` being reported when it can't be one.
+    fn check_map_key(
+        &self, 
+        key: &TokenSet, 
+        from_module: &[String], 
+        invalid: fn(String) -> DiagnosticKind,
+        diagnostics: &mut Diagnostics) {
+        if !self.is_map_key(key, from_module, &mut vec![]) {
+            let name = key.custom_token_type.clone()
+                .unwrap_or_else(|| key.token_type.keyword()
+                    .unwrap_or_default().into());
+
+            diagnostics.push(Diagnostic::error(
+                invalid(name), Some(key.span.clone()))
+                .with_note("map keys and set items must be strings, \
+                            integers, `local` enums or aliases of them"));
+        }
+    }
+}
+
+// Reports the inclusions of a field group that end up including it, the
+// other cycles being reported by their own field groups.
+fn check_field_group_cycle(
+    field_group: &TokenSet,
+    module_path: &[String],
+    field_groups: &HashMap<String, TokenSet>,
+    diagnostics: &mut Diagnostics) {
+    let qualified_name = format!("{}.{}", 
+                                 module_path.join("."), field_group.token_name);
+
+    for include in field_group.childs.iter()
+        .filter(|child| child.token_type == TokenType::FieldGroup) {
+        let mut pending = vec![include.custom_token_type.clone().unwrap()];
+        let mut visited: Vec<String> = vec![];
+
+        while let Some(name) = pending.pop() {
+            if name == qualified_name {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::RecursiveFieldGroup(
+                        field_group.token_name.clone()),
+                    Some(include.span.clone())));
+                break;
+            }
+            if visited.contains(&name) { continue; }
+            visited.push(name.clone());
+
+            pending.extend(field_groups.get(&name).iter()
+                .flat_map(|field_group| field_group.childs.iter())
+                .filter(|child| child.token_type == TokenType::FieldGroup)
+                .map(|child| child.custom_token_type.clone().unwrap()));
+        }
+    }
+}
+
+// Replaces the field groups included by the structs of a module and of the
+// modules nested in it with their fields, reporting the fields they bring
+// that are already declared. Field groups are then removed, generators only
+// handling ordinary fields.
+fn expand_field_groups(
+    module: &mut TokenSet, 
+    field_groups: &HashMap<String, TokenSet>,
+    diagnostics: &mut Diagnostics) {
+    for declaration in module.childs.iter_mut() {
+        if declaration.token_type == TokenType::Module {
+            expand_field_groups(declaration, field_groups, diagnostics);
+            continue;
+        }
+        if declaration.token_type != TokenType::Structure { continue; }
+
+        let fields = expanded_fields(
+            &declaration.childs, field_groups, &mut vec![]);
+
+        for (index, (field, origin)) in fields.iter().enumerate() {
+            let (first, first_origin) = match fields[..index].iter()
+                .find(|(other, _)| other.token_name == field.token_name) {
+                Some(first) => first,
+                None => continue
+            };
+            // Fields declared twice by the struct itself are left as is.
+            if origin.is_none() && first_origin.is_none() { continue; }
+
+            let span = origin.as_ref()
+                .map(|(_, include)| include.clone())
+                .unwrap_or(field.span.clone());
+            let mut diagnostic = Diagnostic::error(
+                DiagnosticKind::DuplicateField { 
+                    name: field.token_name.clone(), 
+                    origin: first_origin.as_ref()
+                        .map(|(field_group, _)| field_group.clone())
+                        .unwrap_or(declaration.token_name.clone()) },
+                Some(span));
+
+            // Both declarations are pointed at, a field group included twice
+            // being pointed at once.
+            for (field, origin) in [(first, first_origin), (field, origin)] {
+                let note = match origin {
+                    Some((field_group, _)) => format!(
+                        "`{}` declares `{}` at {}", 
+                        field_group, field.token_name, field.span),
+                    None => continue
+                };
+                if !diagnostic.notes.contains(&note) {
+                    diagnostic = diagnostic.with_note(note);
+                }
+            }
+            diagnostics.push(diagnostic);
+        }
+
+        declaration.childs = fields.into_iter()
+            .map(|(field, _)| field).collect();
+    }
+
+    module.childs.retain(|child| child.token_type != TokenType::FieldGroup);
+}
+
+// Gives the fields of the structs and tagged unions of a module and of the
+// modules nested in it the name they are serialized as, reporting the names
+// used twice in a struct or a constant.
+fn resolve_serialized_names(
+    module: &mut TokenSet, diagnostics: &mut Diagnostics) {
+    for declaration in module.childs.iter_mut() {
+        if declaration.token_type == TokenType::Module {
+            resolve_serialized_names(declaration, diagnostics);
+            continue;
+        }
+        if declaration.token_type != TokenType::Structure && 
+            declaration.token_type != TokenType::Enumeration { continue; }
+
+        let rule = find_annotation(&declaration.annotations, "rename_all")
+            .and_then(|annotation| rename_rule(annotation, diagnostics));
+
+        if declaration.token_type == TokenType::Enumeration {
+            for constant in declaration.childs.iter_mut() {
+                resolve_field_names(&mut constant.childs, rule, diagnostics);
+            }
+        } else {
+            resolve_field_names(&mut declaration.childs, rule, diagnostics);
+        }
+    }
+}
+
+// Gives fields the name of their `@rename` annotation, or the one `rule`
+// turns their name into. Other fields are serialized in camel case, so that
+// every generator serializes them under the same name.
+fn resolve_field_names(
+    fields: &mut [TokenSet], 
+    rule: Option<fn(&str) -> String>,
+    diagnostics: &mut Diagnostics) {
+    let mut serialized_names: Vec<(String, String)> = vec![];
+
+    for field in fields.iter_mut() {
+        field.serialized_name = match find_annotation(
+            &field.annotations, "rename") {
+            Some(annotation) => text_argument(
+                annotation, "json", "a name as a string, optionally named \
+                                     `json`", diagnostics),
+            None => Some(rule.map_or_else(
+                || field.token_name.to_lower_camel_case(),
+                |rule| rule(&field.token_name)))
+        };
+
+        let name = match &field.serialized_name {
+            Some(name) => name,
+            None => continue
+        };
+        // Fields declared under the same name are reported as duplicate
+        // fields instead.
+        match serialized_names.iter().find(|(other, _)| other == name) {
+            Some((_, first)) if *first == field.token_name => (),
+            Some((name, first)) => diagnostics.push(Diagnostic::error(
+                DiagnosticKind::DuplicateSerializedName { 
+                    name: name.clone(), field: first.clone() },
+                Some(field.span.clone()))),
+            None => serialized_names.push(
+                (name.clone(), field.token_name.clone()))
+        }
+    }
+}
+
+// Gives the declarations of the modules, along with their fields and
+// constants, the message of their `@deprecated` annotation. Field groups are
+// given theirs before `resolve_local_types` expands them, so that each problem
+// is reported once and that they can be listed.
+pub fn resolve_deprecations(
+    source: &mut [TokenSet], 
+    diagnostics: &mut Diagnostics) {
+    for module in source.iter_mut() {
+        resolve_module_deprecations(module, diagnostics);
+    }
+}
+
+fn resolve_module_deprecations(
+    module: &mut TokenSet, 
+    diagnostics: &mut Diagnostics) {
+    for declaration in module.childs.iter_mut() {
+        if declaration.token_type == TokenType::Module {
+            resolve_module_deprecations(declaration, diagnostics);
+            continue;
+        }
+
+        declaration.deprecation = deprecation_message(
+            &declaration.annotations, diagnostics);
+
+        for child in declaration.childs.iter_mut() {
+            child.deprecation = deprecation_message(
+                &child.annotations, diagnostics);
+        }
+    }
+}
+
+// Message of the `@deprecated` annotation among `annotations`, if any.
+fn deprecation_message(
+    annotations: &[Annotation], 
+    diagnostics: &mut Diagnostics) -> Option<String> {
+    find_annotation(annotations, "deprecated").and_then(|annotation| 
+        text_argument(annotation, "note", "a message as a string, \
+                      optionally named `note`", diagnostics))
+}
+
+// First annotation named `name`, the other ones being ignored.
+fn find_annotation<'a>(annotations: &'a [Annotation], name: &str) 
+    -> Option<&'a Annotation> {
+    annotations.iter().find(|annotation| annotation.name.name == name)
+}
+
+// Conversion a `@rename_all` annotation applies to the names of the fields,
+// reporting the rules it doesn't know.
+fn rename_rule(annotation: &Annotation, diagnostics: &mut Diagnostics) 
+    -> Option<fn(&str) -> String> {
+    let expected = "one of `camelCase`, `snake_case`, `kebab-case` or \
+                    `SCREAMING_SNAKE_CASE`, optionally named `json`";
+
+    match text_argument(annotation, "json", expected, diagnostics)?
+        .as_str() {
+        "camelCase" => Some(|name| name.to_lower_camel_case()),
+        "snake_case" => Some(|name| name.to_snake_case()),
+        "kebab-case" => Some(|name| name.to_kebab_case()),
+        "SCREAMING_SNAKE_CASE" => Some(|name| name.to_shouty_snake_case()),
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::InvalidAnnotationArguments { 
+                    annotation: annotation.name.name.clone(), 
+                    expected: expected.into() },
+                Some(annotation.arguments[0].value.1.clone())));
+            None
+        }
+    }
+}
+
+// Value of an annotation taking a single non-empty string, which may be
+// named `argument_name`, reporting the annotation when it's given anything 
+// else.
+fn text_argument(
+    annotation: &Annotation, 
+    argument_name: &str,
+    expected: &str, 
+    diagnostics: &mut Diagnostics) -> Option<String> {
+    match annotation.arguments.as_slice() {
+        [AnnotationArgument { name, value: (Literal::Text(text), _) }]
+            if !text.is_empty() && name.as_ref()
+                .is_none_or(|name| name.name == argument_name) => 
+            Some(text.clone()),
+        _ => {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::InvalidAnnotationArguments { 
+                    annotation: annotation.name.name.clone(), 
+                    expected: expected.into() },
+                Some(annotation.span.clone())));
+            None
+        }
+    }
+}
+
+// Fields where the field groups they include are replaced by their fields,
+// along with the field group declaring them and the span of the inclusion 
+// they come from, when they come from one. Field groups in `visited` are 
+// skipped, their cycle being reported on its own, and so are unresolved ones.
+fn expanded_fields(
+    fields: &[TokenSet], 
+    field_groups: &HashMap<String, TokenSet>,
+    visited: &mut Vec<String>) 
+    -> Vec<(TokenSet, Option<(String, SourceSpan)>)> {
+    let mut output = vec![];
+
+    for field in fields {
+        if field.token_type != TokenType::FieldGroup {
+            output.push((field.clone(), None));
+            continue;
+        }
+
+        let name = field.custom_token_type.clone().unwrap();
+        let field_group = match field_groups.get(&name) {
+            Some(field_group) if !visited.contains(&name) => field_group,
+            _ => continue
+        };
+
+        visited.push(name.clone());
+        output.extend(expanded_fields(
+            &field_group.childs, field_groups, visited).into_iter()
+            .map(|(group_field, origin)| {
+                let declared_by = origin.map(|(field_group, _)| field_group)
+                    .unwrap_or(name.clone());
+                (group_field, Some((declared_by, field.span.clone())))
+            }));
+        visited.pop();
+    }
+    return output;
+}
+
+// Fields of the struct named `name` and of the structs it extends, or `None`
+// when one of them extends the first struct of `visited`. Structs that can't
+// be found and other cycles are reported on their own.
+fn inherited_fields(
+    name: &str, 
+    structs: &HashMap<String, TokenSet>, 
+    visited: &mut Vec<String>) -> Option<Vec<TokenSet>> {
+    if visited.first().is_some_and(|first| first == name) { return None; }
+    if visited.iter().any(|visited| visited == name) { return Some(vec![]); }
+    let declaration = match structs.get(name) {
+        Some(declaration) => declaration,
+        None => return Some(vec![])
+    };
+
+    visited.push(name.into());
+    let mut output = vec![];
+
+    for base in declaration.bases.iter() {
+        output.extend(inherited_fields(
+            base.custom_token_type.as_ref().unwrap(), structs, visited)?);
+    }
+    output.extend(declaration.childs.iter().cloned());
+    visited.pop();
+
+    return Some(output);
+}
+
+// Reports a field whose name is already used by a field coming from another
+// struct, `origin` being the struct it comes from.
+fn check_field_origin(
+    field: &TokenSet, 
+    origin: &str, 
+    span: &SourceSpan,
+    field_origins: &mut Vec<(String, String)>,
+    diagnostics: &mut Diagnostics) {
+    match field_origins.iter().find(|(name, _)| *name == field.token_name) {
+        Some((name, first_origin)) => diagnostics.push(Diagnostic::error(
+            DiagnosticKind::DuplicateField { 
+                name: name.clone(), origin: first_origin.clone() },
+            Some(span.clone()))),
+        None => field_origins.push(
+            (field.token_name.clone(), origin.to_string()))
+    }
+}
+
+impl ItemDeclarationDescriptor {
+    // Name of the declaration prefixed with the path of its module, such as
+    // `Billing.Invoices.Invoice`.
+    pub fn qualified_name(&self) -> String {
+        return format!("{}.{}", 
+                       self.module_path.join("."), self.declaration_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{lexer::tokenize, parser::Parser};
+
+    // Modules of `source` once resolved, along with the diagnostics found
+    // on the way.
+    fn resolve(source: &str) -> (Vec<TokenSet>, Vec<DiagnosticKind>) {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(source, "test.tc", &mut diagnostics);
+        let modules = Parser::new(&lexemes, &mut diagnostics).parse_file();
+        let mut token_sets = TokenSet::token_sets_from_ast(&modules);
+
+        resolve_deprecations(&mut token_sets, &mut diagnostics);
+        ReusableDeclarations::from_token_sets_vec(token_sets.clone())
+            .resolve_local_types(&mut token_sets, &mut diagnostics);
+
+        return (token_sets, diagnostics.entries.into_iter()
+            .map(|diagnostic| diagnostic.kind).collect());
+    }
+
+    fn serialized_names(declaration: &TokenSet) -> Vec<Option<String>> {
+        declaration.childs.iter()
+            .map(|field| field.serialized_name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn annotation_arguments_can_be_named() {
+        let (modules, diagnostics) = resolve(
+            "module M\n@rename_all(json: \"camelCase\")\nstruct A\n    \
+             @rename(json: \"user_id\")\n    string UserId\n    \
+             @rename(json : \"created\")\n    string CreatedAt\n    \
+             @deprecated(note: \"use `Id`\")\n    string Key\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let declaration = &modules[0].childs[0];
+        assert_eq!(serialized_names(declaration), [
+            Some("user_id".into()), Some("created".into()), 
+            Some("key".into())]);
+        assert_eq!(declaration.childs[2].deprecation.as_deref(), 
+                   Some("use `Id`"));
+    }
+
+    #[test]
+    fn every_declaration_can_be_deprecated() {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(
+            "module M\n@deprecated(\"a\")\nconst int_u8 Max = 1\n\
+             @deprecated(\"b\")\nalias Id = string\n\
+             @deprecated(\"c\")\nnewtype Cents = int_i64\n\
+             @deprecated(\"d\")\nextern Money\n    rs \"f64\"\n\
+             @deprecated(\"e\")\nfields Audit\n    string By\n", 
+            "test.tc", &mut diagnostics);
+        let mut modules = TokenSet::token_sets_from_ast(
+            &Parser::new(&lexemes, &mut diagnostics).parse_file());
+
+        resolve_deprecations(&mut modules, &mut diagnostics);
+        assert!(diagnostics.entries.is_empty(), "{:?}", diagnostics.entries);
+        assert_eq!(modules[0].deprecated_in_tree("").iter()
+            .map(|(path, token)| (path.as_str(), 
+                                  token.deprecation.as_deref().unwrap()))
+            .collect::<Vec<(&str, &str)>>(), [
+                ("M.Max", "a"), ("M.Id", "b"), ("M.Cents", "c"), 
+                ("M.Money", "d"), ("M.Audit", "e")]);
+    }
+
+    #[test]
+    fn map_keys_and_set_items() {
+        let (_, diagnostics) = resolve(
+            "module M\nenum Color\n    Red\nalias Code = int_u16\n\
+             struct A\n    map<string, bool> Flags\n    \
+             map<Color local, string> Names\n    set<Code local> Codes\n    \
+             map<float_32, string> Ratios\n    set<bool> Answers\n    \
+             map<A local, string> Nested\n");
+
+        assert_eq!(diagnostics, [
+            DiagnosticKind::InvalidMapKey("float_32".into()),
+            DiagnosticKind::InvalidSetItem("bool".into()),
+            DiagnosticKind::InvalidMapKey("A".into())]);
+    }
+
+    #[test]
+    fn annotation_arguments_are_checked() {
+        let (_, diagnostics) = resolve(
+            "module M\nstruct A\n    @rename(xml: \"id\")\n    string Id\n    \
+             @rename(\"a\", \"b\")\n    string Other\n");
+
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics.iter().all(|kind| matches!(kind, 
+            DiagnosticKind::InvalidAnnotationArguments { annotation, .. } 
+                if annotation == "rename")));
+    }
+
+    #[test]
+    fn tagged_unions() {
+        let (modules, diagnostics) = resolve(
+            "module M\nstruct Card\n    string Number\n\
+             enum Payment\n    Cash\n    \
+             Transfer { string iban, @rename(\"by-user\") string user }\n    \
+             Card(Card)\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let constants = &modules[0].childs[1].childs;
+        assert!(constants[0].childs.is_empty()
+            && constants[0].payload.is_none());
+        assert_eq!(serialized_names(&constants[1]), [
+            Some("iban".into()), Some("by-user".into())]);
+        assert_eq!(constants[2].payload.as_ref().unwrap()
+            .custom_token_type.as_deref(), Some("M.Card"));
+
+        let (_, diagnostics) = resolve(
+            "module M\nenum Color\n    Red\n\
+             enum Payment\n    Cash(Color)\n    Card(Missing)\n");
+        assert_eq!(diagnostics, [
+            DiagnosticKind::PayloadNotStruct("Color".into()),
+            DiagnosticKind::PayloadNotStruct("Missing".into())]);
+    }
+
+    #[test]
+    fn generic_structs() {
+        let (modules, diagnostics) = resolve(
+            "module M\nstruct User\n    string Name\n\
+             struct Page<T>\n    T vec Items\n    int_u32 Total\n\
+             struct Users\n    Page<User local> local Users\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(modules[0].childs[1].type_parameters, ["T"]);
+        let users = &modules[0].childs[2].childs[0];
+        assert_eq!(users.custom_token_type.as_deref(), Some("M.Page"));
+        assert_eq!(users.type_arguments[0].custom_token_type.as_deref(), 
+                   Some("M.User"));
+
+        let (_, diagnostics) = resolve(
+            "module M\nstruct Page<T>\n    T vec Items\n\
+             struct Users\n    Page local Bare\n    \
+             Page<string, int_u8> local Extra\n");
+        assert_eq!(diagnostics, [
+            DiagnosticKind::TypeArgumentCount { 
+                type_name: "Page".into(), expected: 1, found: 0 },
+            DiagnosticKind::TypeArgumentCount { 
+                type_name: "Page".into(), expected: 1, found: 2 }]);
+    }
+
+    #[test]
+    fn aliases_and_newtypes() {
+        let (modules, diagnostics) = resolve(
+            "module M\nalias UserId = string\nalias Tags = string vec\n\
+             newtype Cents = int_i64\n\
+             struct A\n    UserId local Id\n    Tags local Labels\n    \
+             Cents local Price\n    map<Cents local, UserId local> Owners\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let declarations = &modules[0].childs;
+        assert_eq!(declarations[0].token_type, TokenType::Alias);
+        assert_eq!(declarations[0].childs[0].token_type, TokenType::String);
+        assert_eq!(declarations[2].token_type, TokenType::NewType);
+        assert_eq!(declarations[3].childs[2].custom_token_type.as_deref(), 
+                   Some("M.Cents"));
+
+        let (_, diagnostics) = resolve(
+            "module M\nalias A = B local\nalias B = A local\n\
+             newtype Ratio = float_32\n\
+             struct S\n    map<Ratio local, string> Values\n");
+        assert_eq!(diagnostics, [
+            DiagnosticKind::RecursiveAlias("A".into()),
+            DiagnosticKind::RecursiveAlias("B".into()),
+            DiagnosticKind::InvalidMapKey("Ratio".into())]);
+    }
+
+    #[test]
+    fn qualified_references() {
+        let (modules, diagnostics) = resolve(
+            "module Billing\nenum Status\n    Paid\n\
+             module Shipping\nenum Status\n    Sent\n\
+             struct Parcel\n    Status local Status\n    \
+             Billing.Status Payment\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let parcel = &modules[1].childs[1];
+        assert_eq!(parcel.childs.iter()
+            .map(|field| field.custom_token_type.as_deref().unwrap())
+            .collect::<Vec<&str>>(), ["Shipping.Status", "Billing.Status"]);
+
+        let (_, diagnostics) = resolve(
+            "module Billing\nenum Status\n    Paid\n\
+             module Shipping\nenum Status\n    Sent\n\
+             module Orders\nstruct Order\n    Status local Status\n    \
+             Billing.Missing Payment\n");
+        assert_eq!(diagnostics, [
+            DiagnosticKind::AmbiguousLocalType { 
+                name: "Status".into(), 
+                candidates: vec!["Billing.Status".into(), 
+                                 "Shipping.Status".into()] },
+            DiagnosticKind::UnresolvedLocalType("Billing.Missing".into())]);
+    }
+
+    #[test]
+    fn extended_structs() {
+        let (modules, diagnostics) = resolve(
+            "module M\nstruct Audited\n    uuid Id\n    datetime CreatedAt\n\
+             struct Order extends Audited\n    string Reference\n\
+             struct Refund extends Order\n    int_u32 Amount\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let refund = &modules[0].childs[2];
+        assert_eq!(refund.bases[0].custom_token_type.as_deref(), 
+                   Some("M.Order"));
+        assert_eq!(refund.bases[0].childs.iter()
+            .map(|field| field.token_name.as_str())
+            .collect::<Vec<&str>>(), ["Id", "CreatedAt", "Reference"]);
+
+        let (_, diagnostics) = resolve(
+            "module M\nenum Color\n    Red\nstruct Page<T>\n    T Item\n\
+             struct A extends B\n    string Id\n\
+             struct B extends A\n    string Name\n\
+             struct C extends Color\n    string Name\n\
+             struct D extends Page\n    string Name\n\
+             struct Audited\n    uuid Id\n\
+             struct E extends Audited\n    string Id\n");
+        assert_eq!(diagnostics, [
+            DiagnosticKind::BaseNotStruct("Color".into()),
+            DiagnosticKind::GenericBase("Page".into()),
+            DiagnosticKind::RecursiveBase("A".into()),
+            DiagnosticKind::RecursiveBase("B".into()),
+            DiagnosticKind::DuplicateField { 
+                name: "Id".into(), origin: "M.Audited".into() }]);
+    }
+
+    #[test]
+    fn field_groups() {
+        let (modules, diagnostics) = resolve(
+            "module M\nfields Named\n    string Name\n\
+             fields Auditable\n    include Named\n    uuid Id\n\
+             @rename_all(\"camelCase\")\nstruct Order\n    \
+             include Auditable\n    string Reference\n");
+
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(modules[0].childs.len(), 1);
+        assert_eq!(serialized_names(&modules[0].childs[0]), [
+            Some("name".into()), Some("id".into()), 
+            Some("reference".into())]);
+
+        let (_, diagnostics) = resolve(
+            "module M\nstruct Base\n    string Id\n\
+             fields Loop\n    include Loop\n\
+             fields Named\n    string Name\n\
+             struct A\n    include Base\n    Named local Other\n    \
+             include Named\n    string Name\n");
+        assert_eq!(diagnostics, [
+            DiagnosticKind::NotFieldGroup("Base".into()),
+            DiagnosticKind::FieldGroupAsType("Named".into()),
+            DiagnosticKind::RecursiveFieldGroup("Loop".into()),
+            DiagnosticKind::DuplicateField { 
+                name: "Name".into(), origin: "M.Named".into() }]);
+    }
+
+    #[test]
+    fn duplicate_fields_point_at_the_field_group() {
+        let mut diagnostics = Diagnostics::default();
+        let lexemes = tokenize(
+            "module M\nfields Named\n    string Name\n\
+             struct A\n    string Name\n    include Named\n", 
+            "test.tc", &mut diagnostics);
+        let mut modules = TokenSet::token_sets_from_ast(
+            &Parser::new(&lexemes, &mut diagnostics).parse_file());
+        ReusableDeclarations::from_token_sets_vec(modules.clone())
+            .resolve_local_types(&mut modules, &mut diagnostics);
 
-        if struct_search.is_some() { return struct_search }
-        else if enum_search.is_some() { return enum_search }
-        else { None } 
+        let diagnostic = &diagnostics.entries[0];
+        assert_eq!(diagnostic.span.as_ref().unwrap().line, 6);
+        assert_eq!(diagnostic.notes, [
+            "`M.Named` declares `Name` at test.tc:3:5"]);
     }
 }
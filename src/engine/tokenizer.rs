@@ -1,4 +1,8 @@
 use crate::{utils::conditions::make_rule_set, debug};
+use super::ast::{
+    Alias, Annotation, Constraint, Extern, Field, Ident, Item, Literal, Member,
+    Module, TypeRef, Variant, VariantPayload, quote_char};
+use super::diagnostics::{Diagnostic, DiagnosticKind, SourceSpan};
 
 // Type of the token.
 #[derive(Debug, PartialEq, Clone)]
@@ -6,6 +10,13 @@ pub enum TokenType {
     Module,
     Structure,
     Enumeration,
+    Alias,
+    NewType,
+    Constant,
+    Extern,
+    // Group of fields included in structs, expanded once resolved. Inclusions
+    // are stored in the fields of a struct as local types of this type.
+    FieldGroup,
     String,
     Char,
     IntU8,
@@ -16,18 +27,42 @@ pub enum TokenType {
     IntI16,
     IntI32,
     IntI64,
+    IntU128,
+    IntI128,
+    // Integer of arbitrary precision.
+    BigInt,
+    Float32,
+    Float64,
     Bool,
+    // Collections of items of the type they are given.
+    Vector,
+    Array,
+    Set,
+    // Fixed list of items of the types it is given, in order.
+    Tuple,
+    // Well-known types, mapped by each generator to a type of its language.
+    Bytes,
+    Uuid,
+    DateTime,
+    Date,
+    Duration,
+    Decimal,
+    Map,
     Custom
 }
 
 // Additional tokens that can help define the usage of the token.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenParameter {
+    // Deprecated, replaced by the parser with the matching float type.
     Floated,
     Pointer,
     Reference,
     Vector,
+    // Can be absent once serialized.
     Optional,
+    // Can be null once serialized.
+    Nullable,
     LocalType
 }
 
@@ -37,31 +72,46 @@ pub struct TokenSet {
     pub custom_token_type: Option<String>,
     pub parameters: Vec<TokenParameter>,
     pub token_name: String,
-    pub childs: Vec<TokenSet>
+    pub childs: Vec<TokenSet>,
+    // Lines of the doc comments written above the token.
+    pub docs: Vec<String>,
+    // Annotations written above the token.
+    pub annotations: Vec<Annotation>,
+    // Name a field is serialized as, from its `@rename` annotation or the
+    // `@rename_all` rule of its struct, fields of tagged union constants
+    // being serialized under their own name otherwise. Generators name fields
+    // without one as they usually do.
+    pub serialized_name: Option<String>,
+    // Message of the `@deprecated` annotation of a declaration, a field or an
+    // enum constant.
+    pub deprecation: Option<String>,
+    // Value given to an enum constant or a constant, or default value of a
+    // field.
+    pub value: Option<Literal>,
+    // Struct wrapped by a tagged union constant, its inline fields being
+    // stored in `childs` instead.
+    pub payload: Option<Box<TokenSet>>,
+    // Types given as type arguments, such as the key and value of a map.
+    pub type_arguments: Vec<TokenSet>,
+    // Number of items of a fixed-size array.
+    pub array_length: Option<usize>,
+    // Structs a struct extends, as local types. Once resolved, they hold the
+    // fields they bring, including the ones of the structs they extend.
+    pub bases: Vec<TokenSet>,
+    // Names of the type parameters a generic struct is declared with.
+    pub type_parameters: Vec<String>,
+    // Constraints the value of a field must follow.
+    pub constraints: Vec<Constraint>,
+    pub span: SourceSpan
 }
 
-impl TokenSet {
-    // Builds a `TokenSet` from a line.
-    pub fn token_set_from_string(line: String) -> Option<Self> {
-        // Lines with too few characters are ignored.
-        if line.trim().len() < 2 { return None; }
-
-        let mut tokens = line.trim().split(" ").map(|i| i.to_string())
-            .collect::<Vec<String>>();
-   
-        // Fails if the set of tokens is not long enough.
-        if tokens.len() < 1 {
-            panic!("Too few arguments on line: {}", tokens.join(" "));
-
-        }
-
-        // Build a ruleset to find which token is used on this line.
+impl TokenType {
+    // Finds the type matching a field type keyword, custom types being any
+    // word that isn't a keyword.
+    pub fn from_keyword(keyword: &str) -> Self {
         let mut token_type = make_rule_set::<TokenType, String>(
-            tokens.get(0).unwrap().clone());
+            keyword.to_string());
         
-        token_type.exec_rule(TokenType::Module,      "module".into());
-        token_type.exec_rule(TokenType::Structure,   "struct".into());
-        token_type.exec_rule(TokenType::Enumeration, "enum".into());
         token_type.exec_rule(TokenType::String,      "string".into());
         token_type.exec_rule(TokenType::Char,        "char".into());
         token_type.exec_rule(TokenType::IntU8,       "int_u8".into());
@@ -72,95 +122,577 @@ impl TokenSet {
         token_type.exec_rule(TokenType::IntI16,      "int_i16".into());
         token_type.exec_rule(TokenType::IntI32,      "int_i32".into());
         token_type.exec_rule(TokenType::IntI64,      "int_i64".into());        
+        token_type.exec_rule(TokenType::IntU128,     "int_u128".into());
+        token_type.exec_rule(TokenType::IntI128,     "int_i128".into());
+        token_type.exec_rule(TokenType::BigInt,      "bigint".into());
+        token_type.exec_rule(TokenType::Float32,     "float_32".into());
+        token_type.exec_rule(TokenType::Float64,     "float_64".into());
         token_type.exec_rule(TokenType::Bool,        "bool".into());
+        token_type.exec_rule(TokenType::Bytes,       "bytes".into());
+        token_type.exec_rule(TokenType::Uuid,        "uuid".into());
+        token_type.exec_rule(TokenType::DateTime,    "datetime".into());
+        token_type.exec_rule(TokenType::Date,        "date".into());
+        token_type.exec_rule(TokenType::Duration,    "duration".into());
+        token_type.exec_rule(TokenType::Decimal,     "decimal".into());
+        token_type.exec_rule(TokenType::Map,         "map".into());
+        token_type.exec_rule(TokenType::Vector,      "vec".into());
+        token_type.exec_rule(TokenType::Array,       "array".into());
+        token_type.exec_rule(TokenType::Set,         "set".into());
+        token_type.exec_rule(TokenType::Tuple,       "tuple".into());
 
-        // Determines if there are token parameters present on the line.
-        let mut token_parameters: Vec<TokenParameter> = vec![];
-        
-        if tokens.len() > 2 {
-            tokens.iter().for_each(|tok| {
-                let mut rule_set = make_rule_set::
-                    <TokenParameter, String>(tok.clone());
-                
-                rule_set.exec_rule(TokenParameter::Vector,    "vec".into());
-                rule_set.exec_rule(TokenParameter::Floated,   "floated".into());
-                rule_set.exec_rule(TokenParameter::Pointer,   "pointer".into());
-                rule_set.exec_rule(TokenParameter::Reference, "ref".into());
-                rule_set.exec_rule(TokenParameter::LocalType, "local".into());
-                rule_set.exec_rule(
-                    TokenParameter::Optional,  "optional".into());
-
-                // If a rule worked, the result is pushed into `
-                // token_parameters`.
-                if rule_set.value.is_some() { 
-                    token_parameters.push(rule_set.value.unwrap()); 
-                }
-            });
-        };
+        token_type.value.unwrap_or(TokenType::Custom)
+    }
+
+    // Keyword a type or a declaration is written with, custom types having
+    // none.
+    pub fn keyword(&self) -> Option<&'static str> {
+        match self {
+            TokenType::Module => Some("module"),
+            TokenType::Structure => Some("struct"),
+            TokenType::Enumeration => Some("enum"),
+            TokenType::Alias => Some("alias"),
+            TokenType::NewType => Some("newtype"),
+            TokenType::Constant => Some("const"),
+            TokenType::Extern => Some("extern"),
+            TokenType::FieldGroup => Some("fields"),
+            TokenType::String => Some("string"),
+            TokenType::Char => Some("char"),
+            TokenType::IntU8 => Some("int_u8"),
+            TokenType::IntU16 => Some("int_u16"),
+            TokenType::IntU32 => Some("int_u32"),
+            TokenType::IntU64 => Some("int_u64"),
+            TokenType::IntI8 => Some("int_i8"),
+            TokenType::IntI16 => Some("int_i16"),
+            TokenType::IntI32 => Some("int_i32"),
+            TokenType::IntI64 => Some("int_i64"),
+            TokenType::IntU128 => Some("int_u128"),
+            TokenType::IntI128 => Some("int_i128"),
+            TokenType::BigInt => Some("bigint"),
+            TokenType::Float32 => Some("float_32"),
+            TokenType::Float64 => Some("float_64"),
+            TokenType::Bool => Some("bool"),
+            TokenType::Vector => Some("vec"),
+            TokenType::Array => Some("array"),
+            TokenType::Set => Some("set"),
+            TokenType::Tuple => Some("tuple"),
+            TokenType::Bytes => Some("bytes"),
+            TokenType::Uuid => Some("uuid"),
+            TokenType::DateTime => Some("datetime"),
+            TokenType::Date => Some("date"),
+            TokenType::Duration => Some("duration"),
+            TokenType::Decimal => Some("decimal"),
+            TokenType::Map => Some("map"),
+            TokenType::Custom => None
+        }
+    }
 
-        // As the name of the Token should always be at the end of the line,
-        // we extract the last entry from the splitted line as the Token Name.
-        let token_name = tokens.pop().unwrap(); 
+    // Keyword of a well-known type, the one its mapping is overridden with
+    // in the configuration file.
+    pub fn well_known_keyword(&self) -> Option<&'static str> {
+        match self {
+            TokenType::Bytes => Some("bytes"),
+            TokenType::Uuid => Some("uuid"),
+            TokenType::DateTime => Some("datetime"),
+            TokenType::Date => Some("date"),
+            TokenType::Duration => Some("duration"),
+            TokenType::Decimal => Some("decimal"),
+            _ => None
+        }
+    }
 
-        // If the TokenType value is None, it means that a custom type is used,
-        // thus should be set.
-        let final_token_type = token_type.value.unwrap_or(TokenType::Custom);
-        let custom_token_type = if final_token_type == TokenType::Custom {
-            Some(token_type.to_compare) } else { None };
+    // Tells if the type is a well-known type, such as `uuid`.
+    pub fn is_well_known(&self) -> bool {
+        self.well_known_keyword().is_some()
+    }
 
-        debug!("Build item data: {} {:?}", token_name, final_token_type);
+    // Tells if the type is an integer wider than the 53 bits JavaScript 
+    // numbers hold.
+    pub fn is_wide_integer(&self) -> bool {
+        matches!(self, TokenType::IntU64 | TokenType::IntI64 
+                 | TokenType::IntU128 | TokenType::IntI128 | TokenType::BigInt)
+    }
 
-        return Some(Self {
-            token_type: final_token_type,
-            custom_token_type,
-            parameters: token_parameters,
-            token_name,
-            childs: vec![]
-        });
+    // Tells if the type is an integer some languages can only hold in an
+    // object, such as Go `*big.Int`s.
+    pub fn is_big_integer(&self) -> bool {
+        matches!(self, TokenType::IntU128 | TokenType::IntI128 
+                 | TokenType::BigInt)
+    }
+}
+
+impl TokenParameter {
+    // Finds the parameter matching a keyword.
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        let mut rule_set = make_rule_set::<TokenParameter, String>(
+            keyword.to_string());
+        
+        rule_set.exec_rule(TokenParameter::Vector,    "vec".into());
+        rule_set.exec_rule(TokenParameter::Floated,   "floated".into());
+        rule_set.exec_rule(TokenParameter::Pointer,   "pointer".into());
+        rule_set.exec_rule(TokenParameter::Reference, "ref".into());
+        rule_set.exec_rule(TokenParameter::LocalType, "local".into());
+        rule_set.exec_rule(TokenParameter::Optional,  "optional".into());
+        rule_set.exec_rule(TokenParameter::Nullable,  "nullable".into());
+
+        rule_set.value
+    }
+
+    // Keyword the parameter is written with.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            TokenParameter::Floated => "floated",
+            TokenParameter::Pointer => "pointer",
+            TokenParameter::Reference => "ref",
+            TokenParameter::Vector => "vec",
+            TokenParameter::Optional => "optional",
+            TokenParameter::Nullable => "nullable",
+            TokenParameter::LocalType => "local"
+        }
+    }
+}
+
+impl TokenSet {
+    // Builds an unnamed token of type `token_type`, without any child,
+    // parameter or value.
+    pub fn new(token_type: TokenType, span: SourceSpan) -> Self {
+        Self {
+            token_type,
+            custom_token_type: None,
+            parameters: vec![],
+            token_name: String::new(),
+            childs: vec![],
+            docs: vec![],
+            annotations: vec![],
+            serialized_name: None,
+            deprecation: None,
+            value: None,
+            payload: None,
+            type_arguments: vec![],
+            array_length: None,
+            bases: vec![],
+            type_parameters: vec![],
+            constraints: vec![],
+            span
+        }
     }
 
-    // Group tokens together in `childs` according to Token hierarchy:
+    // Builds the tokens hierarchy from parsed modules:
     // - Modules
-    //      - Structs / Enums
+    //      - Nested modules
+    //      - Structs / Enums / Aliases / Newtypes / Constants / Externs
     //          - Types
-    pub fn apply_hierarchy_rules(
-        tokens_vec: Vec<TokenSet>) -> Vec<TokenSet> {
-        let mut output: Vec<TokenSet> = vec![]; 
-
-        tokens_vec.iter().for_each(|token| {
-            match token.token_type {
-                TokenType::Module => { 
-                    output.push(token.clone());
+    // Modules declared more than once are merged, and the modules a nested
+    // module is declared in are created when they aren't declared.
+    pub fn token_sets_from_ast(modules: &[Module]) -> Vec<TokenSet> {
+        let mut output: Vec<TokenSet> = vec![];
+
+        for module in modules {
+            let mut siblings = &mut output;
+
+            for (depth, name) in module.path.iter().enumerate() {
+                let position = match siblings.iter().position(|token| 
+                    token.token_type == TokenType::Module && 
+                    token.token_name == name.name) {
+                    Some(position) => position,
+                    None => {
+                        siblings.push(Self {
+                            token_name: name.name.clone(),
+                            ..Self::new(TokenType::Module, name.span.clone())
+                        });
+                        siblings.len() - 1
+                    }
+                };
+                let token = &mut siblings[position];
+
+                if depth == module.path.len() - 1 {
+                    token.childs.extend(
+                        module.items.iter().map(Self::token_set_from_item));
+                    token.docs.extend(module.docs.iter().cloned());
+                    token.annotations.extend(
+                        module.annotations.iter().cloned());
+                    token.span = module.span.clone();
                 }
-                TokenType::Structure | TokenType::Enumeration => {
-                    if output.len() < 1 {
-                        panic!("Struct/enum have to be defined under a module.");
+                siblings = &mut token.childs;
+            }
+        }
+
+        return output;
+    }
+
+    fn token_set_from_item(item: &Item) -> Self {
+        match item {
+            Item::Struct(declaration) => Self {
+                token_name: declaration.name.name.clone(),
+                childs: declaration.members.iter()
+                    .map(Self::token_set_from_member).collect(),
+                docs: declaration.docs.clone(),
+                annotations: declaration.annotations.clone(),
+                bases: declaration.bases.iter()
+                    .map(Self::token_set_from_base).collect(),
+                type_parameters: declaration.type_parameters.iter()
+                    .map(|parameter| parameter.name.clone()).collect(),
+                ..Self::new(TokenType::Structure, declaration.span.clone())
+            },
+            Item::Enum(declaration) => {
+                // Once a constant has a value, every constant of the enum gets
+                // one. Missing integers count up from the previous constant.
+                let valued = declaration.variants.iter()
+                    .any(|variant| variant.value.is_some());
+                let mut next_value = 0;
+                let childs = declaration.variants.iter().map(|variant| {
+                    let mut token = Self::token_set_from_variant(variant);
+
+                    if valued && token.value.is_none() {
+                        token.value = Some(Literal::Integer(next_value));
                     }
-               
-                    // Push the struct/enum at the top of the module.
-                    output.last_mut().unwrap()
-                        .childs.push(token.clone());
-                },
-                _ => {
-                    // Push the type at the top of the last struct pushed into
-                    // the module.
-                    output.last_mut().unwrap()
-                        .childs.last_mut().unwrap()
-                        .childs.push(token.clone());
+                    if let Some(Literal::Integer(value)) = token.value {
+                        next_value = value + 1;
+                    }
+                    token
+                }).collect();
+
+                Self {
+                    token_name: declaration.name.name.clone(),
+                    childs,
+                    docs: declaration.docs.clone(),
+                    annotations: declaration.annotations.clone(),
+                    ..Self::new(TokenType::Enumeration, 
+                                declaration.span.clone())
                 }
-            }; 
-        });
-        
+            }
+            Item::Alias(declaration) => 
+                Self::token_set_from_alias(declaration, TokenType::Alias),
+            Item::NewType(declaration) => 
+                Self::token_set_from_alias(declaration, TokenType::NewType),
+            Item::Extern(declaration) => 
+                Self::token_set_from_extern(declaration),
+            Item::FieldGroup(declaration) => Self {
+                token_name: declaration.name.name.clone(),
+                childs: declaration.members.iter()
+                    .map(Self::token_set_from_member).collect(),
+                docs: declaration.docs.clone(),
+                annotations: declaration.annotations.clone(),
+                ..Self::new(TokenType::FieldGroup, declaration.span.clone())
+            },
+            // Constants hold their type as their only child.
+            Item::Const(declaration) => Self {
+                token_name: declaration.name.name.clone(),
+                childs: vec![Self::new(
+                    TokenType::from_keyword(&declaration.const_type.name),
+                    declaration.const_type.span.clone())],
+                docs: declaration.docs.clone(),
+                annotations: declaration.annotations.clone(),
+                value: Some(declaration.value.0.clone()),
+                ..Self::new(TokenType::Constant, declaration.span.clone())
+            }
+        }
+    }
+
+    // Aliases and newtypes hold the type they stand for as their only child.
+    fn token_set_from_alias(declaration: &Alias, token_type: TokenType) 
+        -> Self {
+        Self {
+            token_name: declaration.name.name.clone(),
+            childs: vec![Self::token_set_from_type(&declaration.target)],
+            docs: declaration.docs.clone(),
+            annotations: declaration.annotations.clone(),
+            ..Self::new(token_type, declaration.span.clone())
+        }
+    }
+
+    // Extern types hold a token per language they are mapped to, named after
+    // the language and standing for the mapped type. The imports the type
+    // requires are the childs of that token.
+    fn token_set_from_extern(declaration: &Extern) -> Self {
+        let named_token = |name: &str, custom_type: &str| Self {
+            custom_token_type: Some(custom_type.into()),
+            token_name: name.into(),
+            ..Self::new(TokenType::Custom, declaration.span.clone())
+        };
+
+        Self {
+            token_type: TokenType::Extern,
+            childs: declaration.mappings.iter().map(|mapping| Self {
+                childs: mapping.imports.iter()
+                    .map(|import| named_token("", import)).collect(),
+                span: mapping.span.clone(),
+                ..named_token(&mapping.language.name, &mapping.target)
+            }).collect(),
+            custom_token_type: None,
+            docs: declaration.docs.clone(),
+            annotations: declaration.annotations.clone(),
+            ..named_token(&declaration.name.name, "")
+        }
+    }
+
+    // Inclusions of field groups are stored as local types, replaced by the
+    // fields of the group once resolved.
+    fn token_set_from_member(member: &Member) -> Self {
+        match member {
+            Member::Field(field) => Self::token_set_from_field(field),
+            Member::Include(name) => Self {
+                custom_token_type: Some(name.name.clone()),
+                parameters: vec![TokenParameter::LocalType],
+                ..Self::new(TokenType::FieldGroup, name.span.clone())
+            }
+        }
+    }
+
+    fn token_set_from_field(field: &Field) -> Self {
+        debug!("Build item data: {} {:?}", field.name.name, field.field_type);
+
+        Self {
+            token_name: field.name.name.clone(),
+            docs: field.docs.clone(),
+            annotations: field.annotations.clone(),
+            value: field.default.as_ref().map(|(value, _)| value.clone()),
+            constraints: field.constraints.iter()
+                .map(|(constraint, _)| constraint.clone()).collect(),
+            span: field.span.clone(),
+            ..Self::token_set_from_type(&field.field_type)
+        }
+    }
+
+    // Type arguments are stored as unnamed tokens.
+    fn token_set_from_type(type_ref: &TypeRef) -> Self {
+        // If the type isn't a keyword, it means that a custom type is used,
+        // thus should be set.
+        let token_type = TokenType::from_keyword(&type_ref.name.name);
+        let custom_token_type = if token_type == TokenType::Custom {
+            Some(type_ref.name.name.clone()) } else { None };
+        let mut parameters: Vec<TokenParameter> = type_ref.parameters.iter()
+            .map(|(parameter, _)| parameter.clone()).collect();
+
+        // Qualified names can only point to TypeCode declarations.
+        if type_ref.name.name.contains('.') && 
+            !parameters.contains(&TokenParameter::LocalType) {
+            parameters.push(TokenParameter::LocalType);
+        }
+
+        Self {
+            custom_token_type,
+            parameters,
+            type_arguments: type_ref.arguments.iter()
+                .map(Self::token_set_from_type).collect(),
+            array_length: type_ref.array_length,
+            ..Self::new(token_type, type_ref.span.clone())
+        }
+    }
+
+    // Extended structs are stored as local types, their fields being added
+    // once resolved.
+    fn token_set_from_base(base: &Ident) -> Self {
+        Self {
+            custom_token_type: Some(base.name.clone()),
+            parameters: vec![TokenParameter::LocalType],
+            ..Self::new(TokenType::Custom, base.span.clone())
+        }
+    }
+
+    // Enum constants are stored as custom types named after themselves.
+    fn token_set_from_variant(variant: &Variant) -> Self {
+        let (childs, payload) = match &variant.payload {
+            Some(VariantPayload::Fields(fields)) => 
+                (fields.iter().map(Self::token_set_from_field).collect(), None),
+            // The wrapped struct is stored as a local type.
+            Some(VariantPayload::Struct(name)) => (vec![], Some(Box::new(Self {
+                custom_token_type: Some(name.name.clone()),
+                parameters: vec![TokenParameter::LocalType],
+                token_name: variant.name.name.clone(),
+                ..Self::new(TokenType::Custom, name.span.clone())
+            }))),
+            None => (vec![], None)
+        };
+
+        Self {
+            custom_token_type: Some(variant.name.name.clone()),
+            token_name: variant.name.name.clone(),
+            childs,
+            docs: variant.docs.clone(),
+            annotations: variant.annotations.clone(),
+            value: variant.value.as_ref().map(|(value, _)| value.clone()),
+            payload,
+            ..Self::new(TokenType::Custom, variant.span.clone())
+        }
+    }
+
+    // Lists the declarations of a module and of the modules nested in it,
+    // along with the path of the module they are declared in.
+    pub fn declarations(&self) -> Vec<(Vec<String>, &TokenSet)> {
+        let mut output = vec![];
+
+        for child in &self.childs {
+            if child.token_type != TokenType::Module {
+                output.push((vec![self.token_name.clone()], child));
+                continue;
+            }
+
+            for (mut path, declaration) in child.declarations() {
+                path.insert(0, self.token_name.clone());
+                output.push((path, declaration));
+            }
+        }
+        return output;
+    }
+
+    // Lists the types used by a declaration, its fields, their type
+    // arguments, payloads and the fields of the structs it extends.
+    pub fn used_types(&self) -> Vec<TokenType> {
+        let mut output = vec![self.token_type.clone()];
+
+        for token in self.childs.iter()
+            .chain(self.type_arguments.iter())
+            .chain(self.bases.iter())
+            .chain(self.payload.iter().map(|payload| payload.as_ref())) {
+            for token_type in token.used_types() {
+                if !output.contains(&token_type) { output.push(token_type); }
+            }
+        }
         return output;
     }
 
-    // Searches for a child TokenSet which is a struct. This function will
-    // return `None` if it's not ran from a Module instance.
-    pub fn find_struct_in_token_childs(&self, struct_name: String) 
-        -> Option<&TokenSet> {
-         if self.token_type != TokenType::Module { return None }
+    // Lists the numbers of items of the tuples used by a declaration, its 
+    // fields, their type arguments and payloads.
+    pub fn tuple_lengths(&self) -> Vec<usize> {
+        let mut output = vec![];
+
+        if self.token_type == TokenType::Tuple {
+            output.push(self.type_arguments.len());
+        }
+        for token in self.childs.iter()
+            .chain(self.type_arguments.iter())
+            .chain(self.payload.iter().map(|payload| payload.as_ref())) {
+            for length in token.tuple_lengths() {
+                if !output.contains(&length) { output.push(length); }
+            }
+        }
+        return output;
+    }
+
+    // Lists the annotations of a token and of the tokens it's made of.
+    pub fn annotations_in_tree(&self) -> Vec<&Annotation> {
+        self.annotations.iter()
+            .chain(self.childs.iter()
+                   .flat_map(|child| child.annotations_in_tree()))
+            .collect()
+    }
+
+    // Lists the deprecated tokens of a token and of the tokens it's made of,
+    // along with their path from the outermost module, such as
+    // `Billing.Invoice.Total`.
+    pub fn deprecated_in_tree(&self, parent_path: &str) 
+        -> Vec<(String, &TokenSet)> {
+        let path = if parent_path.is_empty() { self.token_name.clone() } 
+            else { format!("{}.{}", parent_path, self.token_name) };
+        let mut output = vec![];
+
+        if self.deprecation.is_some() { output.push((path.clone(), self)); }
+        for child in &self.childs {
+            output.extend(child.deprecated_in_tree(&path));
+        }
+        return output;
+    }
+
+    // Tells if the token is a declaration that can be placed in a module.
+    pub fn is_declaration(&self) -> bool {
+        matches!(self.token_type, TokenType::Structure | TokenType::Enumeration
+                 | TokenType::Alias | TokenType::NewType | TokenType::Constant
+                 | TokenType::Extern | TokenType::FieldGroup)
+    }
+
+    // Finds the mapping of an extern type to `language`, which is required
+    // to generate code in that language.
+    pub fn extern_mapping(&self, language: &str) 
+        -> Result<&TokenSet, Diagnostic> {
+        self.childs.iter()
+            .find(|mapping| mapping.token_name == language)
+            .ok_or(Diagnostic::error(
+                DiagnosticKind::MissingExternMapping { 
+                    name: self.token_name.clone(), 
+                    language: language.into() },
+                Some(self.span.clone())))
+    }
+
+    // Lists the imports the mapping of an extern type to `language`
+    // requires.
+    pub fn extern_imports(&self, language: &str) -> Vec<String> {
+        match self.extern_mapping(language) {
+            Ok(mapping) => mapping.childs.iter()
+                .filter_map(|import| import.custom_token_type.clone())
+                .collect(),
+            Err(_) => vec![]
+        }
+    }
+
+    // Writes the value of a constant or the default value of a field as a
+    // literal of `language`, among `rs`, `go` and `ts`.
+    pub fn value_code(&self, language: &str) -> String {
+        let value_type = if self.token_type == TokenType::Constant { 
+            &self.childs[0].token_type } else { &self.token_type };
+
+        match (value_type, self.value.as_ref().unwrap()) {
+            (TokenType::Char, Literal::Text(text)) => 
+                quote_char(text.chars().next().unwrap(), language),
+            (_, value) => value.to_code(language)
+        }
+    }
+
+    // Tells if a struct has fields with default values.
+    pub fn has_default_values(&self) -> bool {
+        self.token_type == TokenType::Structure && 
+            self.childs.iter().any(|child| child.value.is_some())
+    }
+
+    // Copy of a struct whose fields are preceded by the ones of the structs
+    // it extends, for languages without a way to extend structs.
+    pub fn with_inherited_fields(&self) -> TokenSet {
+        let mut output = self.clone();
+
+        output.childs = self.bases.iter()
+            .flat_map(|base| base.childs.iter().cloned())
+            .chain(self.childs.iter().cloned())
+            .collect();
+        output.bases = vec![];
+        return output;
+    }
+
+    // Tells if a field can be absent once serialized, vectors of optional
+    // values being always present.
+    pub fn is_optional_field(&self) -> bool {
+        self.parameters.contains(&TokenParameter::Optional) && 
+            !self.parameters.contains(&TokenParameter::Vector)
+    }
+
+    // Tells if a field can be null once serialized, vectors of nullable
+    // values never being null.
+    pub fn is_nullable_field(&self) -> bool {
+        self.parameters.contains(&TokenParameter::Nullable) && 
+            !self.parameters.contains(&TokenParameter::Vector)
+    }
+
+    // Tells if a struct has fields with constraints.
+    pub fn has_constraints(&self) -> bool {
+        self.token_type == TokenType::Structure && 
+            self.childs.iter().any(|child| !child.constraints.is_empty())
+    }
+
+    // Tells if an enum is a tagged union, with constants carrying data.
+    pub fn is_tagged_union(&self) -> bool {
+        self.token_type == TokenType::Enumeration && self.childs.iter()
+            .any(|child| child.payload.is_some() || !child.childs.is_empty())
+    }
+
+    // Tells if the first constant of an enum carries no data, making it a
+    // fitting default value.
+    pub fn has_unit_first_constant(&self) -> bool {
+        self.token_type == TokenType::Enumeration && self.childs.first()
+            .is_some_and(|child| child.payload.is_none() && 
+                         child.childs.is_empty())
+    }
 
-         self.childs.iter().find_map(|item| if item.token_name == struct_name 
-                                     { Some(item) } else { None })
+    // Tells if the constants of an enum are valued with integers.
+    pub fn has_integer_values(&self) -> bool {
+        self.childs.iter()
+            .any(|child| matches!(child.value, Some(Literal::Integer(_))))
     }
 }
@@ -1,10 +1,8 @@
 #[macro_export]
 macro_rules! debug {
     ($($args:tt)*) => (
-        unsafe {
-            if crate::DEBUG_ENABLED {
-                println!("{}", format_args!($($args)*))
-            }
+        if $crate::DEBUG_ENABLED.load(std::sync::atomic::Ordering::Relaxed) {
+            println!("{}", format_args!($($args)*))
         }
     );
 }
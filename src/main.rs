@@ -1,25 +1,32 @@
+// Explicit `return` statements are part of this codebase's style.
+#![allow(clippy::needless_return)]
+// Diagnostics are returned by value, as they are only built once per problem.
+#![allow(clippy::result_large_err)]
+
+use std::sync::atomic::{AtomicBool, Ordering};
 use utils::clargs;
 use crate::{
     utils::conf_file::{
         try_detect_conf_file_within_provided_directory, 
         read_configuration_from_to_config_struct}, 
-    config::CommandLineInstructions};
+    config::CommandLineInstructions,
+    engine::{
+        diagnostics::{Diagnostics, SourceMap},
+        generator::{GeneratorOptions, WideIntegers}}};
 
 mod config;
 mod engine;
 mod utils;
 mod macros;
 
-static mut DEBUG_ENABLED: bool = false;
+static DEBUG_ENABLED: AtomicBool = AtomicBool::new(false);
 
 fn main() {
     let cli_args = clargs::cli_args_to_string_vec();
 
     // Environment settings
-    unsafe {
-        DEBUG_ENABLED = clargs::is_argument_present_on_args_string_vec(
-            &cli_args, "--debug".into());
-    }
+    DEBUG_ENABLED.store(clargs::is_argument_present_on_args_string_vec(
+            &cli_args, "--debug".into()), Ordering::Relaxed);
 
     // Command-line variables
     let dir_path = cli_args.get(1)
@@ -36,21 +43,18 @@ fn main() {
     if try_detect_conf_file_within_provided_directory(dir_path.clone()) {
         let conf_file_content = read_configuration_from_to_config_struct(
             dir_path.clone());
-        if conf_file_content.ts.is_some() {
-            tpl_instrs.push(conf_file_content.make_command_line_instruction(
-                    "ts", &dir_path).unwrap()); 
-        }
-        
-        if conf_file_content.rs.is_some() {
-            tpl_instrs.push(conf_file_content.make_command_line_instruction(
-                    "rs", &dir_path).unwrap());
-        }
-
-        if conf_file_content.go.is_some() {
-            tpl_instrs.push(conf_file_content.make_command_line_instruction(
-                    "go", &dir_path).unwrap());
+        for lang in ["ts", "rs", "go"] {
+            match conf_file_content.make_command_line_instruction(
+                lang, dir_path) {
+                Ok(Some(instruction)) => tpl_instrs.push(instruction),
+                Ok(None) => (),
+                Err(diagnostic) => {
+                    Diagnostics { entries: vec![diagnostic] }
+                        .report(&SourceMap::default());
+                    std::process::exit(1);
+                }
+            }
         }
-        
     } else {
         let (_, output) = clargs::argument_and_param_from_args_string_vec(
             &cli_args, "-o".into())
@@ -60,7 +64,7 @@ fn main() {
             .expect("Missing language output: ");
         let mut go_module_name = None;
 
-        if lang == "go".to_string() {
+        if lang == "go" {
             go_module_name = Some(
                 clargs::argument_and_param_from_args_string_vec(
                     &cli_args, 
@@ -69,20 +73,53 @@ fn main() {
                .1); 
         }
 
+        let generator_options = GeneratorOptions {
+            serde: clargs::is_argument_present_on_args_string_vec(
+                &cli_args, "--rs-serde".into()),
+            tag_field: clargs::argument_and_param_from_args_string_vec(
+                &cli_args, "--tag-field".into())
+                .map(|(_, tag_field)| tag_field)
+                .unwrap_or(GeneratorOptions::default().tag_field),
+            btree_map: clargs::is_argument_present_on_args_string_vec(
+                &cli_args, "--rs-btree-map".into()),
+            wide_integers: clargs::argument_and_param_from_args_string_vec(
+                &cli_args, "--wide-integers".into())
+                .map(|(_, keyword)| WideIntegers::from_keyword(&keyword)
+                    .expect("Unknown `--wide-integers` strategy, expected \
+                            `number`, `bigint` or `string`."))
+                .unwrap_or(GeneratorOptions::default().wide_integers),
+            ..GeneratorOptions::default()
+        };
+
         tpl_instrs.push(CommandLineInstructions { 
             transpile_to_lang: lang, 
             transpile_dir_path: dir_path.clone(), 
             transpile_to_output: format!("{}/{}", dir_path.clone(), output), 
-            go_module_name });
+            go_module_name,
+            generator_options,
+            list_deprecated: false });
+    }
+
+    // Deprecated items are listed once, as every instruction reads the same
+    // files.
+    if let Some(first) = tpl_instrs.first_mut() {
+        first.list_deprecated = clargs::is_argument_present_on_args_string_vec(
+            &cli_args, "--list-deprecated".into());
     }
 
-    // Process every transpilation instruction.
+    // Process every transpilation instruction, and exits with an error code
+    // if any of them failed.
+    let mut failed = false;
+
     tpl_instrs.iter().for_each(|tpl| {
         println!("Processing files from {} with the {} generator, and outputs to {}.",
                  dir_path, tpl.transpile_to_lang, tpl.transpile_to_output);
-        tpl.transpile();
+        failed |= !tpl.transpile();
     });
 
+    if failed {
+        std::process::exit(1);
+    }
 
 }
 
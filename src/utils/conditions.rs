@@ -16,7 +16,7 @@ pub fn make_rule_set<T, V: PartialEq>(to_compare: V) -> RuleSet::<T, V> {
 
 impl RuleSet<String, String> {
     #[allow(dead_code)]
-    pub fn exec_rule(&mut self, if_true: String, equals: String) -> () {
+    pub fn exec_rule(&mut self, if_true: String, equals: String) {
         if self.to_compare == equals { 
             self.value = Some(if_true);
         }
@@ -25,7 +25,7 @@ impl RuleSet<String, String> {
 
 impl RuleSet<TokenType, String> {
     #[allow(dead_code)]
-    pub fn exec_rule(&mut self, if_true: TokenType, equals: String) -> () {
+    pub fn exec_rule(&mut self, if_true: TokenType, equals: String) {
         if self.to_compare == equals {
             self.value = Some(if_true);
         }
@@ -34,7 +34,7 @@ impl RuleSet<TokenType, String> {
 
 impl RuleSet<TokenParameter, String> {
     #[allow(dead_code)]
-    pub fn exec_rule(&mut self, if_true: TokenParameter, equals: String) -> () {
+    pub fn exec_rule(&mut self, if_true: TokenParameter, equals: String) {
         if self.to_compare == equals {
             self.value = Some(if_true);
         }
@@ -1,4 +1,4 @@
-use std::{fs, path::Path};
+use std::fs;
 use serde_yaml::from_slice;
 use crate::config::TranspilerExternalConfig;
 
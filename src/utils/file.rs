@@ -13,10 +13,11 @@ pub fn try_read_file_to_bytes(path: String) -> Result<Vec<u8>, String> {
     }
 }
 
-// Read every file from a directory.
+// Read every file from a directory, along with their path. Files are sorted
+// by path to keep the output stable across runs.
 #[allow(dead_code)]
 pub fn try_read_files_from_dir_to_bytes(path: String) 
-    -> Result<Vec<Vec<u8>>, String> {
+    -> Result<Vec<(String, Vec<u8>)>, String> {
 
     debug!("Reading files from directory {}.", path);
     debug!("Excluding any file not ending in .tc");
@@ -24,8 +25,8 @@ pub fn try_read_files_from_dir_to_bytes(path: String)
     match read_dir(path) {
         Ok(dir_content) => {
             let mut output_files = vec![];
-            dir_content.for_each(|file| {
-                let file = file.unwrap();
+            for file in dir_content {
+                let file = file.map_err(|reason| reason.to_string())?;
 
                 // if the file is a folder, the entry is not processed.
                 if file.metadata().unwrap().is_file() &&
@@ -33,13 +34,15 @@ pub fn try_read_files_from_dir_to_bytes(path: String)
 
                     debug!("{:?}", file);
 
-                    match try_read_file_to_bytes(
-                        format!("{}", file.path().display())) {
-                        Ok(content) => { output_files.push(content); }
-                        Err(reason) => { panic!("{}", reason.to_string()); }
-                    }
+                    let file_path = format!("{}", file.path().display());
+
+                    let content = try_read_file_to_bytes(file_path.clone())
+                        .map_err(|reason| 
+                            format!("{}: {}", file_path, reason))?;
+                    output_files.push((file_path, content));
                 }
-            });
+            }
+            output_files.sort_by(|a, b| a.0.cmp(&b.0));
             Ok(output_files)
         } 
         Err(reason) => { Err(reason.to_string()) }
@@ -50,9 +53,7 @@ pub fn try_read_files_from_dir_to_bytes(path: String)
 #[allow(dead_code)]
 pub fn try_write_bytes_to_file(path: String, content: &[u8]) 
     -> Result<(), String> {
-    if let Err(reason) = try_make_path(path.clone(), true) {
-        return Err(reason);
-    }
+    try_make_path(path.clone(), true)?;
     
     match write(path, content) {
         Ok(()) => { Ok(()) }